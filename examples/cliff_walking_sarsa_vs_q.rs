@@ -0,0 +1,79 @@
+use forger::prelude::*;
+use forger::env::cliffwalking::CliffWalking;
+use forger::env::gridworld::GridWorldAction;
+
+pub type S = (usize, usize);
+pub type A = GridWorldAction;
+pub type P = EGreedyPolicy<A>;
+pub type E = CliffWalking;
+
+fn arrow(action: &A) -> char {
+    match action {
+        GridWorldAction::Up => '↑',
+        GridWorldAction::Down => '↓',
+        GridWorldAction::Left => '←',
+        GridWorldAction::Right => '→',
+    }
+}
+
+/// Train one agent per run with a fixed ε = 0.1 and return the mean return per episode and the last agent
+fn run<G: Trainable<S, A, P, E>>(runs: usize, episodes: usize, new_agent: impl Fn() -> G) -> (Vec<f64>, G) {
+    let mut returns = vec![0f64; episodes];
+    let mut last = None;
+    for run in 0..runs {
+        let seed = run as u64;
+        let mut env = Episodic::from(CliffWalking::new()).with_max_steps(1000).with_seed(seed);
+        let mut agent = new_agent();
+        let mut policy = P::new(0.1, 1.0).with_seed(seed);
+        let history = Trainer::<S, A, G, P>::new(episodes).train(&mut agent, &mut policy, &mut env);
+        for (total, r) in returns.iter_mut().zip(history.returns.iter()) {
+            *total += r / runs as f64;
+        }
+        last = Some(agent);
+    }
+    (returns, last.unwrap())
+}
+
+fn render_greedy<G: Agent<S, A, P, E>>(cliff: &E, agent: &G) -> String {
+    cliff.render(|s| {
+        if cliff.is_goal(s) {
+            return None;
+        }
+        cliff
+            .available_actions(s)
+            .into_iter()
+            .fold(None, |best: Option<(A, f64)>, a| {
+                let q = agent.get_action_value(s, &a);
+                if best.is_none_or(|(_, q_best)| q > q_best) { Some((a, q)) } else { best }
+            })
+            .map(|(a, _)| arrow(&a))
+    })
+}
+
+fn main() {
+    // Sutton & Barto, Example 6.6
+    let runs = 20;
+    let episodes = 500;
+    let cliff = CliffWalking::new();
+
+    let (sarsa_returns, sarsa) = run(runs, episodes, || SARSA::<S, A, P, E>::new(1.0, 0.5, 0.0));
+    let (q_returns, q) = run(runs, episodes, || QTD0::<S, A, P, E>::new(1.0, 0.5, 0.0));
+
+    // On-policy SARSA accounts for its own ε-greedy slips and earns more online
+    println!("Mean return per episode while learning (ε = 0.1, {} runs)", runs);
+    println!("{:>9} {:>10} {:>10}", "episodes", "SARSA", "Q-learning");
+    for start in (0..episodes).step_by(100) {
+        let mean = |returns: &[f64]| returns[start..start + 100].iter().sum::<f64>() / 100f64;
+        println!(
+            "{:>4}-{:<4} {:>10.1} {:>10.1}",
+            start + 1,
+            start + 100,
+            mean(&sarsa_returns),
+            mean(&q_returns)
+        );
+    }
+
+    // ... while off-policy Q-learning learns the optimal path along the cliff edge
+    println!("\nGreedy policy (SARSA)\n{}", render_greedy(&cliff, &sarsa));
+    println!("Greedy policy (Q-learning)\n{}", render_greedy(&cliff, &q));
+}
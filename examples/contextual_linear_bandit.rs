@@ -0,0 +1,49 @@
+use forger::prelude::*;
+use forger::bandit::simulate_contextual;
+use forger::bandit::testbed::LinearBandit;
+
+/// Context-free baseline: plays a `BanditAgent` and ignores the features
+struct ContextFree<G>(G);
+
+impl<G: BanditAgent> ContextualBanditAgent for ContextFree<G> {
+    fn select_arm(&mut self, _context: &[f64]) -> usize {
+        self.0.select_arm()
+    }
+
+    fn update(&mut self, _context: &[f64], arm: usize, reward: f64) {
+        self.0.update(arm, reward)
+    }
+}
+
+fn report<G: ContextualBanditAgent>(name: &str, runs: usize, rounds: usize, k: usize, d: usize, agent: impl Fn(u64) -> G) {
+    let mut optimal = 0f64;
+    let mut regret = 0f64;
+    for run in 0..runs {
+        let seed = run as u64;
+        let mut bandit = LinearBandit::random(k, d, seed).with_noise_std(0.5);
+        let history = simulate_contextual(&mut agent(seed), &mut bandit, rounds);
+
+        let tail = rounds / 10;
+        optimal += history.optimal[rounds - tail..].iter().filter(|o| **o).count() as f64 / (tail * runs) as f64;
+        regret += history.regret[rounds - 1] / runs as f64;
+    }
+    println!("{:<24} {:>9.1}% {:>9.2}", name, optimal * 100f64, regret);
+}
+
+fn main() {
+    let k = 5;
+    let d = 8;
+    let runs = 20;
+    let rounds = 2000;
+
+    println!("Linear contextual bandit ({} arms, {} features, {} runs, {} rounds)", k, d, runs, rounds);
+    println!("{:<24} {:>10} {:>9}", "agent", "optimal", "regret");
+    report("UCB1 (context-free)", runs, rounds, k, d, |seed| {
+        ContextFree(ActionValueBandit::new(k, UCBPolicy::new(2.0, 1.0).with_seed(seed)))
+    });
+    report("LinUCB (α = 0.5)", runs, rounds, k, d, |_| LinUCB::new(k, d, 0.5));
+    report("LinUCB (α = 2)", runs, rounds, k, d, |_| LinUCB::new(k, d, 2.0));
+    report("Linear TS (v = 0.5)", runs, rounds, k, d, |seed| {
+        LinearThompson::new(k, d, 0.5).with_seed(seed)
+    });
+}
@@ -0,0 +1,43 @@
+use forger::prelude::*;
+use forger::env::gridworld::{GridWorld, GridWorldAction};
+
+fn main() {
+    let env = GridWorld::new(4, 4, (0, 0), (3, 3), vec![(1, 3), (3, 1)]).with_slip(0.2);
+    let dp = DP::new(0.95).with_tolerance(1e-10);
+
+    // Uniform random policy
+    let v_random = dp.evaluate_policy(&env, |s| {
+        let actions = env.available_actions(s);
+        let p = 1f64 / actions.len() as f64;
+        actions.into_iter().map(|a| (a, p)).collect::<Vec<_>>()
+    });
+
+    let vi = dp.value_iteration(&env);
+    let pi = dp.policy_iteration(&env);
+
+    println!("Value iteration: {} sweeps", vi.iterations);
+    println!("Policy iteration: {} improvement steps", pi.iterations);
+
+    println!("\nV (random) | V* | π*");
+    for y in (0..4).rev() {
+        for x in 0..4 {
+            let s = (x, y);
+            let a = match vi.policy.get(&s) {
+                Some(GridWorldAction::Up) => "↑",
+                Some(GridWorldAction::Down) => "↓",
+                Some(GridWorldAction::Left) => "←",
+                Some(GridWorldAction::Right) => "→",
+                None => " ",
+            };
+            print!("{:>7.3} {:>7.3} {}  ", v_random[&s], vi.value_function[&s], a);
+        }
+        println!();
+    }
+
+    let max_diff = vi
+        .value_function
+        .iter()
+        .map(|(s, v)| (v - pi.value_function[s]).abs())
+        .fold(0f64, f64::max);
+    println!("\nmax |V_VI - V_PI| = {:.2e}", max_diff);
+}
@@ -0,0 +1,53 @@
+use forger::prelude::*;
+use forger::env::gridworld::{GridWorld, GridWorldAction};
+
+pub type S = (usize, usize);
+pub type A = GridWorldAction;
+pub type P = EGreedyPolicy<A>;
+pub type E = GridWorld;
+pub type G = DynaQ<S, A, P, E>;
+
+/// Mean episode length per episode, averaged over runs
+fn mean_lengths(runs: usize, episodes: usize, n_planning: usize, kappa: f64, env: impl Fn(usize) -> E) -> Vec<f64> {
+    let mut lengths = vec![0f64; episodes];
+    for run in 0..runs {
+        let seed = run as u64;
+        let mut agent = G::new(0.95, 0.1, 0.0, n_planning).with_bonus(kappa).with_seed(seed);
+        let mut policy = P::new(0.1, 1.0).with_seed(seed);
+
+        // The environment may change between episodes
+        for (episode, length) in lengths.iter_mut().enumerate() {
+            let mut episodic = Episodic::from(env(episode)).with_max_steps(1000).with_seed(seed + episode as u64);
+            let history = Trainer::<S, A, G, P>::new(1).train(&mut agent, &mut policy, &mut episodic);
+            *length += history.lengths[0] as f64 / runs as f64;
+        }
+    }
+    lengths
+}
+
+fn main() {
+    let runs = 10;
+
+    // Planning: the more planning steps per real step, the fewer episodes to reach the shortest path (14 steps)
+    let episodes = 30;
+    let grid = GridWorld::new(8, 8, (0, 0), (7, 7), vec![(3, 3), (3, 4), (4, 3)]);
+    println!("Mean steps per episode (every 5th episode)");
+    for n in [0, 5, 50] {
+        let lengths = mean_lengths(runs, episodes, n, 0.0, |_| grid.clone());
+        let lengths = lengths.iter().step_by(5).map(|l| format!("{:>6.1}", l)).collect::<Vec<_>>();
+        println!("n = {:>2}: {}", n, lengths.join(" "));
+    }
+
+    // Non-stationary: the goal moves to the opposite corner after 50 episodes
+    let episodes = 100;
+    let shifting = |episode: usize| {
+        let goal = if episode < 50 { (7, 7) } else { (7, 0) };
+        GridWorld::new(8, 8, (0, 0), goal, vec![(3, 3), (3, 4), (4, 3)])
+    };
+    println!("\nMean steps per episode after the goal moves (episodes 50 - 99)");
+    for (name, kappa) in [("Dyna-Q ", 0.0), ("Dyna-Q+", 1e-3)] {
+        let lengths = mean_lengths(runs, episodes, 20, kappa, shifting);
+        let after = lengths[50..].iter().sum::<f64>() / 50f64;
+        println!("{}: {:.1}", name, after);
+    }
+}
@@ -0,0 +1,34 @@
+use peroxide::fuga::*;
+use forger::prelude::*;
+use forger::trainer::ExplorationDecay;
+use forger::env::gridworld::{GridWorld, GridWorldAction};
+
+pub type S = (usize, usize);
+pub type A = GridWorldAction;
+pub type P = EGreedyPolicy<A>;
+pub type E = GridWorld;
+pub type G = ExpectedSARSA<S, A, P, E>;
+
+fn main() {
+    let mut env: Episodic<S, A, E> = Episodic::from(GridWorld::new(4, 4, (0, 0), (3, 3), vec![(1, 3), (3, 1)]))
+        .with_max_steps(200)
+        .with_seed(42);
+    let mut agent = G::new(0.95, 0.5, 0.0);
+    let mut policy = P::new(0.5, 0.99).with_seed(42);
+
+    let mut trainer = Trainer::<S, A, G, P>::new(200).with_callback(ExplorationDecay);
+    let history = trainer.train(&mut agent, &mut policy, &mut env);
+
+    let history_len_vec = history
+        .lengths
+        .iter()
+        .map(|len| *len as u64)
+        .collect::<Vec<_>>();
+    history_len_vec.print();
+
+    // Sort Q table via key
+    let mut q_table = agent.q_table.iter().collect::<Vec<_>>();
+    q_table.sort_by_key(|a| a.0 .0);
+
+    println!("Q Table: {:#?}", q_table);
+}
@@ -0,0 +1,56 @@
+use forger::prelude::*;
+use forger::env::gridworld::{GridWorld, GridWorldAction};
+
+pub type S = (usize, usize);
+pub type A = GridWorldAction;
+pub type P = EGreedyPolicy<A>;
+pub type E = GridWorld;
+pub type G = MCES<S, A, P, E>;
+
+fn main() {
+    let env = GridWorld::new(4, 4, (0, 0), (3, 3), vec![(1, 3), (3, 1)]);
+    let gamma = 0.9;
+
+    // The policy is never used by MC-ES: all exploration comes from the random starts
+    let mut agent = G::new(gamma);
+    let mut policy = P::new(0.0, 1.0).with_seed(42);
+    let mut episodic = Episodic::new(env.clone(), env.get_init_state())
+        .with_max_steps(50)
+        .with_seed(42);
+
+    let mut trainer = Trainer::<S, A, G, P>::new(5000);
+    trainer.train_exploring_starts(&mut agent, &mut policy, &mut episodic);
+
+    let optimal = DP::new(gamma).value_iteration(&env);
+
+    println!("Q(s, π(s)) | V*(s) | π(s)");
+    let mut optimal_actions = 0;
+    let mut total = 0;
+    for y in (0..4).rev() {
+        for x in 0..4 {
+            let s = (x, y);
+            let v_star = optimal.value_function[&s];
+            if env.is_terminal(&s) || env.is_goal(&s) {
+                print!("{:>7} {:>7.3}    ", "", v_star);
+                continue;
+            }
+
+            let a = agent.greedy_action(&s, &env).unwrap();
+            let q_star = DP::new(gamma).action_value(&env, &optimal.value_function, &s, &a);
+            total += 1;
+            if (q_star - v_star).abs() < 1e-6 {
+                optimal_actions += 1;
+            }
+
+            let arrow = match a {
+                GridWorldAction::Up => "↑",
+                GridWorldAction::Down => "↓",
+                GridWorldAction::Left => "←",
+                GridWorldAction::Right => "→",
+            };
+            print!("{:>7.3} {:>7.3} {}  ", agent.get_action_value(&s, &a), v_star, arrow);
+        }
+        println!();
+    }
+    println!("\nOptimal actions: {} / {}", optimal_actions, total);
+}
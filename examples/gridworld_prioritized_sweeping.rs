@@ -0,0 +1,63 @@
+use forger::prelude::*;
+use forger::trainer::EvalSummary;
+use forger::env::gridworld::{GridWorld, GridWorldAction};
+
+pub type S = (usize, usize);
+pub type A = GridWorldAction;
+pub type P = EGreedyPolicy<A>;
+pub type E = GridWorld;
+
+/// Stop once the greedy policy reaches the goal along a shortest path
+struct StopAtOptimal(f64);
+
+impl<G> Callback<S, A, G, P> for StopAtOptimal {
+    fn on_eval(&mut self, summary: &EvalSummary) -> Flow {
+        if summary.mean_steps <= self.0 && summary.mean_return > 0f64 {
+            Flow::Stop
+        } else {
+            Flow::Continue
+        }
+    }
+}
+
+/// Mean number of real steps until the greedy policy is optimal
+fn real_steps<G: Trainable<S, A, P, E>>(runs: usize, grid: &E, optimal_steps: f64, agent: impl Fn(u64) -> G) -> f64 {
+    let mut total = 0f64;
+    for run in 0..runs {
+        let seed = run as u64;
+        let mut env = Episodic::from(grid.clone()).with_max_steps(1000).with_seed(seed);
+        let mut policy = P::new(0.1, 1.0).with_seed(seed);
+        let mut agent = agent(seed);
+
+        let mut trainer = Trainer::<S, A, G, P>::new(1000)
+            .with_eval(1, 1)
+            .with_callback(StopAtOptimal(optimal_steps));
+        let history = trainer.train(&mut agent, &mut policy, &mut env);
+        total += history.lengths.iter().sum::<usize>() as f64 / runs as f64;
+    }
+    total
+}
+
+fn main() {
+    let runs = 5;
+    let n_planning = 5;
+
+    println!("Mean real steps until the greedy policy is optimal ({} planning steps)", n_planning);
+    println!("{:>5} {:>12} {:>12} {:>12}", "size", "Q-learning", "Dyna-Q", "Prioritized");
+    for size in [6, 9, 12] {
+        // A column of pits in the middle, open at the top and bottom rows
+        let pits = (1..size - 1).map(|y| (size / 2, y)).collect::<Vec<_>>();
+        let grid = GridWorld::new(size, size, (0, 0), (size - 1, size - 1), pits);
+        // 2 (size - 1) moves and the final action on the goal
+        let optimal_steps = (2 * (size - 1) + 1) as f64;
+
+        let q = real_steps(runs, &grid, optimal_steps, |seed| QTD0::new(0.95, 0.5, 0.0).with_seed(seed));
+        let dyna = real_steps(runs, &grid, optimal_steps, |seed| {
+            DynaQ::new(0.95, 0.5, 0.0, n_planning).with_seed(seed)
+        });
+        let sweeping = real_steps(runs, &grid, optimal_steps, |_| {
+            PrioritizedSweeping::new(0.95, 0.5, 0.0, n_planning).with_threshold(1e-4)
+        });
+        println!("{:>5} {:>12.0} {:>12.0} {:>12.0}", size, q, dyna, sweeping);
+    }
+}
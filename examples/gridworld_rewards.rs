@@ -0,0 +1,56 @@
+use forger::prelude::*;
+use forger::env::gridworld::{GridWorld, GridWorldAction};
+use forger::env::rewards::Rewards;
+
+pub type S = (usize, usize);
+pub type A = GridWorldAction;
+
+/// Follow a deterministic policy from the initial state and count the steps
+fn rollout(env: &GridWorld, policy: &DPSolution<S, A>) -> (usize, f64) {
+    let mut state = env.get_init_state();
+    let mut steps = 0;
+    let mut total = 0f64;
+    while steps < 100 {
+        let (next_state, reward) = env.transition(&state, &policy.policy.get(&state).copied());
+        steps += 1;
+        total += reward;
+        match next_state {
+            Some(s) => state = s,
+            None => break,
+        }
+    }
+    (steps, total)
+}
+
+fn main() {
+    let grid = || GridWorld::new(5, 5, (0, 0), (4, 4), vec![(1, 3), (3, 1), (2, 2)]);
+
+    // With a step cost, pits must cost more than the way to the goal
+    let shortest = || Rewards::new().with_step(-1.0).with_goal(0.0).with_pit(-10.0);
+    let specs = [
+        ("default (+1 on exit, γ = 1)", Rewards::new()),
+        ("-1 per step, on exit", shortest()),
+        ("-1 per step, on entry", shortest().with_reward_on_entry()),
+        (
+            "on entry, toll on (0,1), (1,0)",
+            shortest()
+                .with_cell((0, 1), -5.0)
+                .with_cell((1, 0), -5.0)
+                .with_reward_on_entry(),
+        ),
+    ];
+
+    println!("{:<30} {:>8} {:>8} {:>8}", "rewards", "V(init)", "steps", "return");
+    for (name, rewards) in specs {
+        let env = grid().with_rewards(rewards);
+        let solution = DP::new(1.0).with_max_iter(1000).value_iteration(&env);
+        let (steps, total) = rollout(&env, &solution);
+        println!(
+            "{:<30} {:>8.2} {:>8} {:>8.2}",
+            name,
+            solution.value_function[&env.get_init_state()],
+            steps,
+            total
+        );
+    }
+}
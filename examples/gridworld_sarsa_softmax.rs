@@ -0,0 +1,45 @@
+use forger::prelude::*;
+use forger::trainer::{EpisodeSummary, ExplorationDecay, OnEpisodeEnd};
+use forger::env::gridworld::{GridWorld, GridWorldAction};
+
+pub type S = (usize, usize);
+pub type A = GridWorldAction;
+pub type P = SoftmaxPolicy<A>;
+pub type E = GridWorld;
+pub type G = SARSA<S, A, P, E>;
+
+fn main() {
+    let grid = GridWorld::new(4, 4, (0, 0), (3, 3), vec![(1, 3), (3, 1)]);
+    let start = grid.get_init_state();
+    let start_actions = grid.available_actions(&start);
+
+    let mut env: Episodic<S, A, E> = Episodic::from(grid).with_max_steps(200).with_seed(42);
+    let mut agent = G::new(0.95, 0.5, 0.0);
+    let mut policy = P::new(1.0, 0.97).with_min_temperature(0.01).with_seed(42);
+
+    // Log the temperature and the entropy of the policy in the start state
+    let log = OnEpisodeEnd(move |summary: &EpisodeSummary, agent: &mut G, policy: &mut P| {
+        if (summary.episode + 1) % 20 == 0 {
+            let candidates = start_actions
+                .iter()
+                .map(|a| (*a, agent.get_action_value(&start, a)))
+                .collect::<Vec<_>>();
+            println!(
+                "episode: {:>3}, steps: {:>3}, τ: {:.4}, H(π(·|start)): {:.4}",
+                summary.episode + 1,
+                summary.steps,
+                policy.get_temperature(),
+                policy.entropy(&candidates)
+            );
+        }
+        Flow::Continue
+    });
+
+    let mut trainer = Trainer::<S, A, G, P>::new(200)
+        .with_callback(log)
+        .with_callback(ExplorationDecay);
+    trainer.train(&mut agent, &mut policy, &mut env);
+
+    let eval = trainer.evaluate(&agent, &mut policy, &mut env, 10);
+    println!("Greedy evaluation: mean steps: {:.2}, mean return: {:.4}", eval.mean_steps, eval.mean_return);
+}
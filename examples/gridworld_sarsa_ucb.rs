@@ -0,0 +1,43 @@
+use peroxide::fuga::*;
+use forger::prelude::*;
+use forger::env::gridworld::{GridWorld, GridWorldAction};
+
+pub type S = (usize, usize);
+pub type A = GridWorldAction;
+pub type P = UCBPolicy<A, S>;
+pub type E = GridWorld;
+pub type G = SARSA<S, A, P, E>;
+
+fn main() {
+    let grid = GridWorld::new(4, 4, (0, 0), (3, 3), vec![(1, 3), (3, 1)]);
+    let start = grid.get_init_state();
+    let start_actions = grid.available_actions(&start);
+
+    let mut env: Episodic<S, A, E> = Episodic::from(grid).with_max_steps(200).with_seed(42);
+    let mut agent = G::new(0.95, 0.5, 0.0);
+    let mut policy = P::new(0.5, 1.0).with_seed(42);
+
+    let mut trainer = Trainer::<S, A, G, P>::new(100);
+    let history = trainer.train(&mut agent, &mut policy, &mut env);
+
+    println!("Episode lengths:");
+    history
+        .lengths
+        .iter()
+        .map(|len| *len as u64)
+        .collect::<Vec<_>>()
+        .print();
+
+    println!("Visits of the start state: {}", policy.state_count(&start));
+    for a in start_actions.iter() {
+        println!(
+            "  {:<5}: N = {:>3}, Q = {:.4}",
+            format!("{:?}", a),
+            policy.action_count(&start, a),
+            agent.get_action_value(&start, a)
+        );
+    }
+
+    let eval = trainer.evaluate(&agent, &mut policy, &mut env, 1);
+    println!("Greedy evaluation: steps: {:.0}, return: {:.4}", eval.mean_steps, eval.mean_return);
+}
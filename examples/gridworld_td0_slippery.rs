@@ -0,0 +1,57 @@
+use peroxide::fuga::*;
+use forger::prelude::*;
+use forger::env::gridworld::{GridWorld, GridWorldAction};
+
+pub type S = (usize, usize);
+pub type A = GridWorldAction;
+pub type P = EGreedyPolicy<A>;
+pub type E = GridWorld;
+
+fn main() {
+    let env = GridWorld::new(4, 4, (0, 0), (3, 3), vec![(1, 3), (3, 1)]).with_slip(0.2);
+    let mut agent = QTD0::<S, A, P, E>::new(0.95, 1f64, 1f64).with_seed(42);
+    let mut policy = EGreedyPolicy::<A>::new(0.9, 0.9).with_seed(42);
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let mut history = Vec::new();
+    for _ in 0..100 {
+        agent.reset_count();
+        let mut episode = vec![];
+        let mut state = env.get_init_state();
+
+        loop {
+            let action = agent.select_action(&state, &mut policy, &env);
+            let (next_state, reward) = env.sample_transition(&state, &action, &mut rng);
+            match next_state {
+                Some(next_state) => {
+                    let step = (state, action.unwrap(), reward, Some(next_state), env.available_actions(&next_state));
+                    agent.update(&step);
+                    episode.push((state, action.unwrap()));
+                    state = next_state
+                }
+                None => {
+                    let step = (state, action.unwrap(), reward, None, vec![]);
+                    agent.update(&step);
+                    episode.push((state, action.unwrap()));
+                    break;
+                }
+            }
+        }
+
+        agent.q_table.iter_mut().for_each(|(_, v)| *v = (*v / (2f64 - agent.gamma)).tanh());
+        history.push(episode);
+        policy.decay_epsilon();
+    }
+
+    let history_len_vec = history
+        .iter()
+        .map(|episode| episode.len() as u64)
+        .collect::<Vec<_>>();
+    history_len_vec.print();
+
+    // Sort Q table via key
+    let mut q_table = agent.q_table.iter().collect::<Vec<_>>();
+    q_table.sort_by_key(|((s, a), _)| (*s, *a as usize));
+
+    println!("Q Table: {:#?}", q_table);
+}
@@ -0,0 +1,45 @@
+use peroxide::fuga::*;
+use forger::prelude::*;
+use forger::trainer::{EarlyStopping, ExplorationDecay, OnEpisodeEnd, OnEpisodeStart, PrintProgress};
+use forger::env::gridworld::{GridWorld, GridWorldAction};
+
+pub type S = (usize, usize);
+pub type A = GridWorldAction;
+pub type P = EGreedyPolicy<A>;
+pub type E = GridWorld;
+pub type G = QTD0<S, A, P, E>;
+
+fn main() {
+    let mut env: Episodic<S, A, E> = Episodic::from(GridWorld::new(4, 4, (0, 0), (3, 3), vec![(1, 3), (3, 1)]))
+        .with_max_steps(200)
+        .with_seed(42);
+    let mut agent = G::new(0.95, 1f64, 1f64).with_seed(42);
+    let mut policy = P::new(0.9, 0.9).with_seed(42);
+
+    let mut trainer = Trainer::<S, A, G, P>::new(100)
+        .with_eval(10, 1)
+        .with_callback(OnEpisodeStart(|_, agent: &mut G, _: &mut P| agent.reset_count()))
+        .with_callback(OnEpisodeEnd(|_: &_, agent: &mut G, _: &mut P| {
+            let gamma = agent.gamma;
+            agent.q_table.iter_mut().for_each(|(_, v)| *v = (*v / (2f64 - gamma)).tanh());
+            Flow::Continue
+        }))
+        .with_callback(ExplorationDecay)
+        .with_callback(EarlyStopping::new(3))
+        .with_callback(PrintProgress::new(10));
+
+    let history = trainer.train(&mut agent, &mut policy, &mut env);
+
+    let history_len_vec = history
+        .lengths
+        .iter()
+        .map(|len| *len as u64)
+        .collect::<Vec<_>>();
+    history_len_vec.print();
+
+    // Sort Q table via key
+    let mut q_table = agent.q_table.iter().collect::<Vec<_>>();
+    q_table.sort_by_key(|a| a.0 .0);
+
+    println!("Q Table: {:#?}", q_table);
+}
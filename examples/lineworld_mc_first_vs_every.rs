@@ -0,0 +1,53 @@
+use forger::prelude::*;
+use forger::env::lineworld::{LineWorld, LineWorldAction};
+
+pub type S = usize;
+pub type A = LineWorldAction;
+pub type P = EGreedyPolicy<A>;
+pub type E = LineWorld;
+
+/// RMS error of a value estimate on the interior states of the random walk
+fn rms_error(agent: &impl Agent<S, A, P, E>, true_values: &[f64]) -> f64 {
+    let n = true_values.len();
+    let mse = (1..n - 1)
+        .map(|s| (agent.get_value(&s) - true_values[s]).powi(2))
+        .sum::<f64>()
+        / (n - 2) as f64;
+    mse.sqrt()
+}
+
+fn main() {
+    // 19-state random walk: V(s) = -1 + 2s / 18
+    let num_states = 19;
+    let true_values = (0..num_states)
+        .map(|s| -1f64 + 2f64 * s as f64 / (num_states - 1) as f64)
+        .collect::<Vec<_>>();
+
+    let runs = 20;
+    let episodes = 100;
+
+    let mut first_rms = 0f64;
+    let mut every_rms = 0f64;
+    for run in 0..runs {
+        let seed = run as u64;
+        let env = || {
+            Episodic::from(LineWorld::new(num_states, num_states / 2, num_states - 1, vec![0])).with_seed(seed)
+        };
+
+        // Both estimators learn from the same episodes
+        let mut first = VFirstVisitMC::<S, A, P, E>::new(1.0);
+        let mut policy = P::new(1.0, 1.0).with_seed(seed);
+        Trainer::new(episodes).train(&mut first, &mut policy, &mut env());
+
+        let mut every = VEveryVisitMC::<S, A, P, E>::new(1.0);
+        let mut policy = P::new(1.0, 1.0).with_seed(seed);
+        Trainer::new(episodes).train(&mut every, &mut policy, &mut env());
+
+        first_rms += rms_error(&first, &true_values) / runs as f64;
+        every_rms += rms_error(&every, &true_values) / runs as f64;
+    }
+
+    println!("RMS error after {} episodes", episodes);
+    println!("First-visit MC: {:.4}", first_rms);
+    println!("Every-visit MC: {:.4}", every_rms);
+}
@@ -0,0 +1,46 @@
+use peroxide::fuga::*;
+use forger::prelude::*;
+use forger::env::lineworld::{LineWorld, LineWorldAction};
+
+pub type S = usize;
+pub type A = LineWorldAction;
+pub type P = EGreedyPolicy<A>;
+pub type E = LineWorld;
+pub type G = VNStepTD<S, A, P, E>;
+
+fn main() {
+    // 19-state random walk: V(s) = -1 + 2s / 18
+    let num_states = 19;
+    let true_values = (0..num_states)
+        .map(|s| -1f64 + 2f64 * s as f64 / (num_states - 1) as f64)
+        .collect::<Vec<_>>();
+
+    let runs = 20;
+    let episodes = 10;
+    let ns = vec![1, 2, 4, 8, 16];
+
+    let mut rms = vec![];
+    for &n in ns.iter() {
+        let mut error = 0f64;
+        for run in 0..runs {
+            let seed = run as u64;
+            let mut env = Episodic::from(LineWorld::new(num_states, num_states / 2, num_states - 1, vec![0]))
+                .with_seed(seed);
+            let mut agent = G::new(n, 1.0, 0.2, 0.0);
+            let mut policy = P::new(1.0, 1.0).with_seed(seed);
+
+            let mut trainer = Trainer::<S, A, G, P>::new(episodes);
+            trainer.train(&mut agent, &mut policy, &mut env);
+
+            let mse = (1..num_states - 1)
+                .map(|s| (agent.get_value(&s) - true_values[s]).powi(2))
+                .sum::<f64>()
+                / (num_states - 2) as f64;
+            error += mse.sqrt() / runs as f64;
+        }
+        rms.push(error);
+    }
+
+    println!("RMS error after {} episodes for n = {:?}", episodes, ns);
+    rms.print();
+}
@@ -0,0 +1,68 @@
+use forger::prelude::*;
+use forger::env::lineworld::{LineWorld, LineWorldAction};
+
+pub type S = usize;
+pub type A = LineWorldAction;
+pub type P = EGreedyPolicy<A>;
+pub type E = LineWorld;
+
+fn main() {
+    let num_states = 7;
+    let gamma = 0.9;
+    let env = LineWorld::new(num_states, num_states / 2, num_states - 1, vec![0]).with_slip(0.2);
+
+    // Target policy: always move Up (towards the goal), except in the goal where only Down is available
+    let goal = env.get_goal_state();
+    let target_action = move |s: &S| if *s == goal { LineWorldAction::Down } else { LineWorldAction::Up };
+    let target = move |s: &S, a: &A| if *a == target_action(s) { 1f64 } else { 0f64 };
+
+    // True action values of the target policy
+    let dp = DP::new(gamma);
+    let v_target = dp.evaluate_policy(&env, |s| vec![(target_action(s), 1f64)]);
+    let q_target = dp.q_values(&env, &v_target);
+
+    let rms_error = |agent: &OffPolicyMCPrediction<S, A, P, E>| {
+        let keys = q_target
+            .keys()
+            .filter(|(s, _)| !env.is_terminal(s) && !env.is_goal(s))
+            .collect::<Vec<_>>();
+        let mse = keys
+            .iter()
+            .map(|(s, a)| (agent.get_action_value(s, a) - q_target[&(*s, *a)]).powi(2))
+            .sum::<f64>()
+            / keys.len() as f64;
+        mse.sqrt()
+    };
+
+    let runs = 20;
+    let episodes = 200;
+
+    // Prediction: evaluate the greedy target from ε-greedy behavior
+    println!("Off-policy MC prediction (RMS error after {} episodes)", episodes);
+    for sampling in [Sampling::Ordinary, Sampling::Weighted] {
+        let mut rms = 0f64;
+        for run in 0..runs {
+            let seed = run as u64;
+            let mut episodic = Episodic::from(env.clone()).with_max_steps(100).with_seed(seed);
+            let mut agent = OffPolicyMCPrediction::<S, A, P, E>::new(gamma, target).with_sampling(sampling);
+            let mut behavior = P::new(0.5, 1.0).with_seed(seed);
+            Trainer::new(episodes).train(&mut agent, &mut behavior, &mut episodic);
+            rms += rms_error(&agent) / runs as f64;
+        }
+        println!("{:?}: {:.4}", sampling, rms);
+    }
+
+    // Control: learn the greedy policy from ε-greedy behavior
+    let mut episodic = Episodic::from(env.clone()).with_max_steps(100).with_seed(0);
+    let mut agent = OffPolicyMCControl::<S, A, P, E>::new(gamma);
+    let mut behavior = P::new(0.3, 1.0).with_seed(0);
+    Trainer::new(1000).train(&mut agent, &mut behavior, &mut episodic);
+
+    println!("\nOff-policy MC control (weighted)");
+    for s in 1..num_states - 1 {
+        let up = agent.get_action_value(&s, &LineWorldAction::Up);
+        let down = agent.get_action_value(&s, &LineWorldAction::Down);
+        let greedy = if up >= down { "Up" } else { "Down" };
+        println!("s = {}: Q(Up) = {:.4}, Q(Down) = {:.4} -> {}", s, up, down, greedy);
+    }
+}
@@ -0,0 +1,34 @@
+use peroxide::fuga::*;
+use forger::prelude::*;
+use forger::env::lineworld::{LineWorld, LineWorldAction};
+
+pub type S = usize;
+pub type A = LineWorldAction;
+pub type P = EGreedyPolicy<A>;
+pub type E = LineWorld;
+pub type G = SARSALambda<S, A, P, E>;
+
+fn main() {
+    // Long corridor: the goal reward has to travel back over many states
+    let num_states = 50;
+    let episodes = 50;
+
+    for trace in [Trace::Accumulating, Trace::Replacing, Trace::Dutch] {
+        let mut env = Episodic::from(LineWorld::new(num_states, 1, num_states - 1, vec![0]))
+            .with_max_steps(1000)
+            .with_seed(42);
+        let mut agent = G::new(0.99, 0.9, 0.1, 0.0).with_trace(trace);
+        let mut policy = P::new(0.1, 1.0).with_seed(42);
+
+        let mut trainer = Trainer::<S, A, G, P>::new(episodes);
+        let history = trainer.train(&mut agent, &mut policy, &mut env);
+
+        println!("{:?} trace - episode lengths", trace);
+        history
+            .lengths
+            .iter()
+            .map(|len| *len as u64)
+            .collect::<Vec<_>>()
+            .print();
+    }
+}
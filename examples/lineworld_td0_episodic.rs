@@ -0,0 +1,51 @@
+use peroxide::fuga::*;
+use forger::prelude::*;
+use forger::env::lineworld::{LineWorld, LineWorldAction};
+
+pub type S = usize;
+pub type A = LineWorldAction;
+pub type P = EGreedyPolicy<A>;
+pub type E = LineWorld;
+
+fn main() {
+    let mut env: Episodic<S, A, E> = Episodic::from(LineWorld::new(10, 1, 9, vec![0]).with_slip(0.1))
+        .with_max_steps(100)
+        .with_seed(42);
+    let mut agent = QTD0::<S, A, P, E>::new(0.95, 1f64, 1f64).with_seed(42);
+    let mut policy = EGreedyPolicy::<A>::new(0.1, 1.0).with_seed(42);
+
+    let mut history = Vec::new();
+    for _ in 0..100 {
+        agent.reset_count();
+        let mut episode = vec![];
+        let mut state = env.reset();
+
+        loop {
+            let action = agent.select_action(&state, &mut policy, env.env()).unwrap();
+            let step = env.step(&action);
+            let next_state = if step.terminated { None } else { Some(step.observation) };
+            agent.update(&(state, action, step.reward, next_state, env.available_actions()));
+            episode.push((state, action));
+
+            if step.is_done() {
+                break;
+            }
+            state = step.observation;
+        }
+
+        agent.q_table.iter_mut().for_each(|(_, v)| *v = (*v / 2f64).tanh());
+        history.push(episode);
+    }
+
+    let history_len_vec = history
+        .iter()
+        .map(|episode| episode.len() as u64)
+        .collect::<Vec<_>>();
+    history_len_vec.print();
+
+    // Sort Q table via key
+    let mut q_table = agent.q_table.iter().collect::<Vec<_>>();
+    q_table.sort_by_key(|a| a.0 .0);
+
+    println!("Q Table: {:#?}", q_table);
+}
@@ -0,0 +1,98 @@
+use peroxide::fuga::*;
+use forger::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Maximization bias MDP (Sutton & Barto, Example 6.7)
+//  - State A (0): `Right` terminates with reward 0, `Left` moves to B with reward 0
+//  - State B (1): every action terminates with reward ~ N(-0.1, 1)
+// └──────────────────────────────────────────────────────────┘
+pub struct MaxBias {
+    num_b_actions: usize,
+}
+
+impl Env<usize, usize> for MaxBias {
+    fn transition(&self, state: &usize, action: &Option<usize>) -> (Option<usize>, f64) {
+        match (state, action) {
+            (0, Some(0)) => (Some(1), 0.0),
+            (0, _) => (None, 0.0),
+            _ => (None, -0.1),
+        }
+    }
+
+    fn is_terminal(&self, _state: &usize) -> bool {
+        false
+    }
+
+    fn is_goal(&self, _state: &usize) -> bool {
+        false
+    }
+
+    fn available_actions(&self, state: &usize) -> Vec<usize> {
+        match state {
+            0 => vec![0, 1],
+            _ => (0..self.num_b_actions).collect(),
+        }
+    }
+
+    fn sample_transition<R: Rng + ?Sized>(
+        &self,
+        state: &usize,
+        action: &Option<usize>,
+        rng: &mut R,
+    ) -> (Option<usize>, f64) {
+        let (next_state, reward) = self.transition(state, action);
+        if *state == 1 {
+            // Box-Muller
+            let u1: f64 = rng.gen::<f64>().max(f64::MIN_POSITIVE);
+            let u2: f64 = rng.gen();
+            let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+            (next_state, reward + z)
+        } else {
+            (next_state, reward)
+        }
+    }
+}
+
+pub type S = usize;
+pub type A = usize;
+pub type P = EGreedyPolicy<A>;
+pub type E = MaxBias;
+pub type G = DoubleQTD0<S, A, P, E>;
+
+/// Record whether `Left` was chosen in state A
+struct LeftCounter(Rc<RefCell<Vec<f64>>>);
+
+impl Callback<S, A, G, P> for LeftCounter {
+    fn on_step(&mut self, _episode: usize, transition: &Transition<S, A>) {
+        if transition.state == 0 {
+            self.0.borrow_mut().push(if transition.action == 0 { 1.0 } else { 0.0 });
+        }
+    }
+}
+
+fn main() {
+    let runs = 100;
+    let episodes = 300;
+
+    let mut left_ratio = vec![0f64; episodes];
+    for run in 0..runs {
+        let seed = run as u64;
+        let mut env = Episodic::new(MaxBias { num_b_actions: 10 }, 0).with_seed(seed);
+        let mut agent = G::new(1.0, 0.1, 0.0).with_seed(seed);
+        let mut policy = P::new(0.1, 1.0).with_seed(seed);
+
+        let left = Rc::new(RefCell::new(vec![]));
+        let mut trainer = Trainer::<S, A, G, P>::new(episodes).with_callback(LeftCounter(left.clone()));
+        trainer.train(&mut agent, &mut policy, &mut env);
+
+        for (ratio, left) in left_ratio.iter_mut().zip(left.borrow().iter()) {
+            *ratio += left / runs as f64;
+        }
+    }
+
+    println!("Fraction of Left from A every 20 episodes (optimal: 0.05)");
+    left_ratio.iter().step_by(20).copied().collect::<Vec<_>>().print();
+}
+
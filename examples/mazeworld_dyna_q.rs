@@ -0,0 +1,64 @@
+use forger::prelude::*;
+use forger::env::gridworld::GridWorldAction;
+use forger::env::mazeworld::MazeWorld;
+
+pub type S = (usize, usize);
+pub type A = GridWorldAction;
+pub type P = EGreedyPolicy<A>;
+pub type E = MazeWorld;
+pub type G = DynaQ<S, A, P, E>;
+
+fn arrow(action: &A) -> char {
+    match action {
+        GridWorldAction::Up => '↑',
+        GridWorldAction::Down => '↓',
+        GridWorldAction::Left => '←',
+        GridWorldAction::Right => '→',
+    }
+}
+
+fn main() {
+    // Dyna maze of Sutton & Barto (Figure 8.2)
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/maps/dyna_maze.txt");
+    let maze = MazeWorld::from_file(path).unwrap();
+
+    let mut env = Episodic::from(maze.clone()).with_max_steps(1000).with_seed(42);
+    let mut agent = G::new(0.95, 0.1, 0.0, 50).with_seed(42);
+    let mut policy = P::new(0.1, 1.0).with_seed(42);
+    let history = Trainer::<S, A, G, P>::new(30).train(&mut agent, &mut policy, &mut env);
+    println!("Dyna-Q (n = 50) steps per episode: {:?}", history.lengths);
+
+    let greedy = |s: &S| {
+        if maze.is_goal(s) {
+            return None;
+        }
+        maze.available_actions(s)
+            .into_iter()
+            .fold(None, |best: Option<(A, f64)>, a| {
+                let q = agent.get_action_value(s, &a);
+                if best.is_none_or(|(_, q_best)| q > q_best) { Some((a, q)) } else { best }
+            })
+            .map(|(a, _)| arrow(&a))
+    };
+    println!("\nGreedy policy (Dyna-Q)\n{}", maze.render(greedy));
+
+    // Two goals: a small reward nearby and a large reward behind the pits
+    let maze = MazeWorld::from_ascii(
+        r"
+        G.....#...
+        ..##..#.X.
+        ..#...#.X.
+        S.#.....XG
+        ",
+    )
+    .unwrap()
+    .with_goal_reward((0, 3), 0.5)
+    .with_goal_reward((9, 0), 2.0)
+    .with_cell_reward((4, 0), -0.5);
+
+    for gamma in [0.8, 0.95] {
+        let solution = DP::new(gamma).value_iteration(&maze);
+        let policy = |s: &S| solution.policy.get(s).filter(|_| !maze.is_goal(s) && !maze.is_terminal(s)).map(arrow);
+        println!("Optimal policy (γ = {})\n{}", gamma, maze.render(policy));
+    }
+}
@@ -0,0 +1,38 @@
+use forger::prelude::*;
+use forger::env::gridworld::GridWorld;
+
+fn main() {
+    // Recycling robot (Sutton & Barto, Example 3.3)
+    let (alpha, beta) = (0.8, 0.4);
+    let (r_search, r_wait) = (2.0, 1.0);
+
+    let robot = TabularMdp::new()
+        .with_transition("high", "search", Some("high"), r_search, alpha)
+        .with_transition("high", "search", Some("low"), r_search, 1.0 - alpha)
+        .with_transition("high", "wait", Some("high"), r_wait, 1.0)
+        .with_transition("low", "search", Some("low"), r_search, beta)
+        .with_transition("low", "search", Some("high"), -3.0, 1.0 - beta)
+        .with_transition("low", "wait", Some("low"), r_wait, 1.0)
+        .with_transition("low", "recharge", Some("high"), 0.0, 1.0);
+    assert!(robot.is_normalized(1e-12));
+
+    println!("States: {:?}", robot.states());
+    println!("Actions: {:?}", robot.state_actions());
+    println!("P(high | low, search) = {}", robot.transition_probability(&"low", &"search", &Some("high")));
+    println!("E[r | low, search] = {}", robot.expected_reward(&"low", &"search"));
+
+    let solution = DP::new(0.9).value_iteration(&robot);
+    println!("V* = {:?}", solution.value_function);
+    println!("π* = {:?}", solution.policy);
+
+    // Any finite MDP can be copied into tables
+    let grid = GridWorld::new(4, 4, (0, 0), (3, 3), vec![(1, 3), (3, 1)]).with_slip(0.1);
+    let table = TabularMdp::from_mdp(&grid);
+    let v_grid = DP::new(0.95).value_iteration(&grid).value_function;
+    let v_table = DP::new(0.95).value_iteration(&table).value_function;
+    let max_diff = v_grid
+        .iter()
+        .map(|(s, v)| (v - v_table[s]).abs())
+        .fold(0f64, f64::max);
+    println!("\nGridWorld as TabularMdp: {} states, max |ΔV*| = {:.2e}", table.num_states(), max_diff);
+}
@@ -0,0 +1,79 @@
+use forger::prelude::*;
+use forger::bandit::simulate;
+use forger::bandit::testbed::{BernoulliBandit, GaussianBandit};
+
+/// Average the final reward, fraction of optimal actions and regret over `runs` bandits
+fn report<B: Bandit, G: BanditAgent>(
+    name: &str,
+    runs: usize,
+    rounds: usize,
+    bandit: impl Fn(u64) -> B,
+    agent: impl Fn(u64) -> G,
+) {
+    let mut reward = 0f64;
+    let mut optimal = 0f64;
+    let mut regret = 0f64;
+    for run in 0..runs {
+        let seed = run as u64;
+        let history = simulate(&mut agent(seed), &mut bandit(seed), rounds);
+
+        // Statistics of the last 10% of rounds
+        let tail = rounds / 10;
+        reward += history.rewards[rounds - tail..].iter().sum::<f64>() / (tail * runs) as f64;
+        optimal += history.optimal[rounds - tail..].iter().filter(|o| **o).count() as f64 / (tail * runs) as f64;
+        regret += history.regret[rounds - 1] / runs as f64;
+    }
+    println!("{:<28} {:>8.4} {:>9.1}% {:>9.2}", name, reward, optimal * 100f64, regret);
+}
+
+fn main() {
+    let k = 10;
+    let runs = 200;
+    let rounds = 1000;
+
+    println!("10-armed Gaussian testbed ({} runs, {} rounds)", runs, rounds);
+    println!("{:<28} {:>8} {:>10} {:>9}", "agent", "reward", "optimal", "regret");
+    let gaussian = |seed| GaussianBandit::testbed(k, seed);
+    report("greedy", runs, rounds, gaussian, |seed| {
+        ActionValueBandit::new(k, EGreedyPolicy::new(0.0, 1.0).with_seed(seed))
+    });
+    report("ε-greedy (ε = 0.1)", runs, rounds, gaussian, |seed| {
+        ActionValueBandit::new(k, EGreedyPolicy::new(0.1, 1.0).with_seed(seed))
+    });
+    report("optimistic greedy (Q0 = 5)", runs, rounds, gaussian, |seed| {
+        ActionValueBandit::new(k, EGreedyPolicy::new(0.0, 1.0).with_seed(seed))
+            .with_step_size(StepSize::Constant(0.1))
+            .with_initial_value(5.0)
+    });
+    report("UCB1 (c = 2)", runs, rounds, gaussian, |seed| {
+        ActionValueBandit::new(k, UCBPolicy::new(2.0, 1.0).with_seed(seed))
+    });
+    report("gradient (α = 0.1)", runs, rounds, gaussian, |seed| {
+        GradientBandit::new(k, 0.1).with_seed(seed)
+    });
+    report("Thompson (Gaussian)", runs, rounds, gaussian, |seed| {
+        GaussianThompson::new(k).with_seed(seed)
+    });
+
+    println!("\nNon-stationary testbed (random walk σ = 0.01, {} rounds)", 5 * rounds);
+    let drifting = |seed| GaussianBandit::new(vec![0f64; k]).with_random_walk(0.01).with_seed(seed);
+    report("sample average (ε = 0.1)", runs, 5 * rounds, drifting, |seed| {
+        ActionValueBandit::new(k, EGreedyPolicy::new(0.1, 1.0).with_seed(seed))
+    });
+    report("constant α = 0.1 (ε = 0.1)", runs, 5 * rounds, drifting, |seed| {
+        ActionValueBandit::new(k, EGreedyPolicy::new(0.1, 1.0).with_seed(seed))
+            .with_step_size(StepSize::Constant(0.1))
+    });
+
+    println!("\n10-armed Bernoulli testbed");
+    let bernoulli = |seed| BernoulliBandit::testbed(k, seed);
+    report("ε-greedy (ε = 0.1)", runs, rounds, bernoulli, |seed| {
+        ActionValueBandit::new(k, EGreedyPolicy::new(0.1, 1.0).with_seed(seed))
+    });
+    report("UCB1 (c = 1)", runs, rounds, bernoulli, |seed| {
+        ActionValueBandit::new(k, UCBPolicy::new(1.0, 1.0).with_seed(seed))
+    });
+    report("Thompson (Beta)", runs, rounds, bernoulli, |seed| {
+        BernoulliThompson::new(k).with_seed(seed)
+    });
+}
@@ -1,10 +1,11 @@
-use std::{collections::HashMap, hash::Hash};
+use std::{cmp::Ordering, collections::{BinaryHeap, HashMap, HashSet, VecDeque}, hash::Hash, ops::{Deref, DerefMut}};
 use peroxide::fuga::*;
 
-use crate::policy::Policy;
+use crate::policy::StatePolicy;
 use crate::env::Env;
+use crate::trainer::{Learning, Trainable, Transition};
 
-pub trait Agent<S, A, P: Policy<A>, E: Env<S, A>> {
+pub trait Agent<S, A, P: StatePolicy<S, A>, E: Env<S, A>> {
     type Information;
     fn select_action(&self, state: &S, policy: &mut P, env: &E) -> Option<A>;
     fn update(&mut self, info: &Self::Information);
@@ -12,21 +13,173 @@ pub trait Agent<S, A, P: Policy<A>, E: Env<S, A>> {
     fn get_action_value(&self, state: &S, action: &A) -> f64;
 }
 
+/// Candidates for value-function agents: expected value of the next state under the transition model
+///
+/// Actions which always end the episode are skipped. In absorbing states (where every action ends
+/// the episode) all actions are returned with zero value, so that the final reward can be collected.
+fn lookahead_candidates<S, A: Clone, E: Env<S, A>>(
+    state: &S,
+    env: &E,
+    value: impl Fn(&S) -> f64,
+) -> Vec<(A, f64)> {
+    let actions = env.available_actions(state);
+    let candidates = actions
+        .iter()
+        .filter_map(|a| {
+            let outcomes = env.transition_distribution(state, &Some(a.clone()));
+            let mut v = 0f64;
+            let mut reachable = false;
+            for (s, _, p) in outcomes.iter() {
+                if let Some(s) = s {
+                    v += p * value(s);
+                    reachable = true;
+                }
+            }
+            if reachable {
+                Some((a.clone(), v))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if candidates.is_empty() {
+        actions.into_iter().map(|a| (a, 0f64)).collect()
+    } else {
+        candidates
+    }
+}
+
+/// Step size rule of Monte Carlo agents, given the visit count `n` (including the current visit)
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum StepSize {
+    /// α = 1 / n (true sample average of returns)
+    SampleAverage,
+    /// α = constant (recency-weighted average)
+    Constant(f64),
+    /// α = c · n^(-η)
+    Decay { c: f64, eta: f64 },
+}
+
+impl StepSize {
+    pub fn alpha(&self, n: usize) -> f64 {
+        match self {
+            StepSize::SampleAverage => 1f64 / n as f64,
+            StepSize::Constant(alpha) => *alpha,
+            StepSize::Decay { c, eta } => c * (n as f64).powf(-eta),
+        }
+    }
+}
+
+/// Best value among the actions recorded for `state` in `q_table` (zero if there are none)
+///
+/// `best` is `f64::max` for agents which maximize and `f64::min` for those which minimize.
+fn best_action_value<S: PartialEq, A>(q_table: &HashMap<(S, A), f64>, state: &S, best: fn(f64, f64) -> f64) -> f64 {
+    q_table
+        .iter()
+        .filter(|((s, _), _)| s == state)
+        .map(|(_, q)| *q)
+        .reduce(best)
+        .unwrap_or(0f64)
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Value Table
+//  - Tabular values of states (`ValueTable<S>`) or state-action pairs (`QTable<S, A>`) of the TD agents
+//  - Step size α = c (t + 1)^(-η), where t counts the updates (`increment_count`)
+//  - Dereferences to the underlying `HashMap` for reading and editing the values
+// └──────────────────────────────────────────────────────────┘
+#[derive(Debug, Clone)]
+pub struct ValueTable<K> {
+    values: HashMap<K, f64>,
+    t: usize,
+    c: f64,
+    eta: f64,
+}
+
+pub type QTable<S, A> = ValueTable<(S, A)>;
+
+impl<K: Hash + Eq> ValueTable<K> {
+    pub fn new(c: f64, eta: f64) -> Self {
+        Self {
+            values: HashMap::new(),
+            t: 0,
+            c,
+            eta,
+        }
+    }
+
+    /// Value of `key` (zero if it was never updated)
+    pub fn value(&self, key: &K) -> f64 {
+        *self.values.get(key).unwrap_or(&0.0)
+    }
+
+    pub fn update_value(&mut self, key: K, value: f64) {
+        self.values.insert(key, value);
+    }
+
+    pub fn add_value(&mut self, key: K, value: f64) {
+        *self.values.entry(key).or_insert(0.0) += value;
+    }
+
+    pub fn increment_count(&mut self) {
+        self.t += 1;
+    }
+
+    pub fn reset_count(&mut self) {
+        self.t = 0;
+    }
+
+    pub fn get_alpha(&self) -> f64 {
+        self.c * (self.t as f64 + 1f64).powf(-self.eta)
+    }
+}
+
+impl<S: PartialEq, A> ValueTable<(S, A)> {
+    /// max_a Q(state, a) over the actions recorded for `state` (zero if there are none)
+    pub fn max_value(&self, state: &S) -> f64 {
+        best_action_value(&self.values, state, f64::max)
+    }
+
+    /// min_a Q(state, a) over the actions recorded for `state` (zero if there are none)
+    pub fn min_value(&self, state: &S) -> f64 {
+        best_action_value(&self.values, state, f64::min)
+    }
+}
+
+impl<K> Deref for ValueTable<K> {
+    type Target = HashMap<K, f64>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.values
+    }
+}
+
+impl<K> DerefMut for ValueTable<K> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.values
+    }
+}
+
 // ┌──────────────────────────────────────────────────────────┐
 //  Value Iteration - Every Visit MC
 // └──────────────────────────────────────────────────────────┘
-pub struct VEveryVisitMC<S, A, P: Policy<A>, E: Env<S, A>> {
+pub struct VEveryVisitMC<S, A, P: StatePolicy<S, A>, E: Env<S, A>> {
     pub value_function: HashMap<S, f64>,
+    pub visit_count: HashMap<S, usize>,
+    step_size: StepSize,
     gamma: f64,
     _action_type: std::marker::PhantomData<A>,
     _policy_type: std::marker::PhantomData<P>,
     _env_type: std::marker::PhantomData<E>,
 }
 
-impl<S: Hash + Eq + Copy, A: Clone, P: Policy<A>, E: Env<S, A>> VEveryVisitMC<S, A, P, E> {
+impl<S: Hash + Eq + Copy, A: Clone, P: StatePolicy<S, A>, E: Env<S, A>> VEveryVisitMC<S, A, P, E> {
     pub fn new(gamma: f64) -> Self {
         Self {
             value_function: HashMap::new(),
+            visit_count: HashMap::new(),
+            step_size: StepSize::SampleAverage,
             gamma,
             _action_type: std::marker::PhantomData,
             _policy_type: std::marker::PhantomData,
@@ -34,12 +187,21 @@ impl<S: Hash + Eq + Copy, A: Clone, P: Policy<A>, E: Env<S, A>> VEveryVisitMC<S,
         }
     }
 
+    pub fn with_step_size(mut self, step_size: StepSize) -> Self {
+        self.step_size = step_size;
+        self
+    }
+
+    pub fn reset_visit_count(&mut self) {
+        self.visit_count.clear();
+    }
+
     pub fn update_value(&mut self, state: &S, value: f64) {
         self.value_function.insert(*state, value);
     }
 }
 
-impl<S: Hash + Eq + Copy, A: Clone, P: Policy<A>, E: Env<S, A>> Agent<S, A, P, E>
+impl<S: Hash + Eq + Copy, A: Clone, P: StatePolicy<S, A>, E: Env<S, A>> Agent<S, A, P, E>
     for VEveryVisitMC<S, A, P, E>
 {
     // Information = Episode
@@ -54,21 +216,8 @@ impl<S: Hash + Eq + Copy, A: Clone, P: Policy<A>, E: Env<S, A>> Agent<S, A, P, E
     }
 
     fn select_action(&self, state: &S, policy: &mut P, env: &E) -> Option<A> {
-        let actions = env.available_actions(state);
-        let candidates = actions
-            .iter()
-            .filter_map(|a| {
-                let (s, _) = env.transition(state, &Some(a.clone()));
-                if let Some(s) = s {
-                    let v = self.get_value(&s);
-                    Some((a.clone(), v))
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<_>>();
-
-        policy.select_action(&candidates)
+        let candidates = lookahead_candidates(state, env, |s| self.get_value(s));
+        policy.select_action_at(state, &candidates)
     }
 
     #[allow(non_snake_case)]
@@ -78,7 +227,7 @@ impl<S: Hash + Eq + Copy, A: Clone, P: Policy<A>, E: Env<S, A>> Agent<S, A, P, E
         }
 
         // Backward update for cumulative discounted return
-        let R: Vec<f64> = info
+        let mut R: Vec<f64> = info
             .iter()
             .rev()
             .scan(0.0, |acc, (_, r)| {
@@ -86,33 +235,52 @@ impl<S: Hash + Eq + Copy, A: Clone, P: Policy<A>, E: Env<S, A>> Agent<S, A, P, E
                 Some(*acc)
             })
             .collect();
+        R.reverse();
 
         // Forward update for value function
-        info.iter().zip(R).enumerate().for_each(|(t, ((s, _), r))| {
+        info.iter().zip(R).for_each(|((s, _), r)| {
+            let n = self.visit_count.entry(*s).or_insert(0);
+            *n += 1;
+            let alpha = self.step_size.alpha(*n);
             let v = self.get_value(s);
-            let alpha = 1.0 / (t + 1) as f64;
             self.update_value(s, v + alpha * (r - v));
         });
     }
 }
 
+impl<S: Hash + Eq + Copy, A: Clone, P: StatePolicy<S, A>, E: Env<S, A>> Trainable<S, A, P, E>
+    for VEveryVisitMC<S, A, P, E>
+{
+    fn learning(&self) -> Learning {
+        Learning::PerEpisode
+    }
+
+    fn information(&self, episode: &[Transition<S, A>], _policy: &P) -> Self::Information {
+        episode.iter().map(|t| (t.state, t.reward)).collect()
+    }
+}
+
 // ┌──────────────────────────────────────────────────────────┐
 //  Q-Learning - Every Visit MC
 // └──────────────────────────────────────────────────────────┘
-pub struct QEveryVisitMC<S, A, P: Policy<A>, E: Env<S, A>> {
+pub struct QEveryVisitMC<S, A, P: StatePolicy<S, A>, E: Env<S, A>> {
     pub q_table: HashMap<(S, A), f64>,
+    pub visit_count: HashMap<(S, A), usize>,
+    step_size: StepSize,
     gamma: f64,
     _action_type: std::marker::PhantomData<A>,
     _policy_type: std::marker::PhantomData<P>,
     _env_type: std::marker::PhantomData<E>,
 }
 
-impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: Policy<A>, E: Env<S, A>>
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>>
     QEveryVisitMC<S, A, P, E>
 {
     pub fn new(gamma: f64) -> Self {
         Self {
             q_table: HashMap::new(),
+            visit_count: HashMap::new(),
+            step_size: StepSize::SampleAverage,
             gamma,
             _action_type: std::marker::PhantomData,
             _policy_type: std::marker::PhantomData,
@@ -120,12 +288,21 @@ impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: Policy<A>, E: Env<S, A>>
         }
     }
 
+    pub fn with_step_size(mut self, step_size: StepSize) -> Self {
+        self.step_size = step_size;
+        self
+    }
+
+    pub fn reset_visit_count(&mut self) {
+        self.visit_count.clear();
+    }
+
     pub fn update_value(&mut self, state: &S, action: &A, value: f64) {
         self.q_table.insert((*state, *action), value);
     }
 }
 
-impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: Policy<A>, E: Env<S, A>> Agent<S, A, P, E>
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Agent<S, A, P, E>
     for QEveryVisitMC<S, A, P, E>
 {
     // Information = Episode
@@ -135,8 +312,9 @@ impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: Policy<A>, E: Env<S, A>> Agent
         *self.q_table.get(&(*state, *action)).unwrap_or(&0.0)
     }
 
-    fn get_value(&self, _state: &S) -> f64 {
-        unimplemented!()
+    /// max_a Q(state, a) over the actions recorded for `state` (zero if there are none)
+    fn get_value(&self, state: &S) -> f64 {
+        best_action_value(&self.q_table, state, f64::max)
     }
 
     fn select_action(&self, state: &S, policy: &mut P, env: &E) -> Option<A> {
@@ -146,7 +324,7 @@ impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: Policy<A>, E: Env<S, A>> Agent
             .map(|a| (*a, self.get_action_value(state, a)))
             .collect::<Vec<_>>();
 
-        policy.select_action(&candidates)
+        policy.select_action_at(state, &candidates)
     }
 
     #[allow(non_snake_case)]
@@ -156,7 +334,7 @@ impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: Policy<A>, E: Env<S, A>> Agent
         }
 
         // Backward update for cumulative discounted return
-        let R: Vec<f64> = info
+        let mut R: Vec<f64> = info
             .iter()
             .rev()
             .scan(0.0, |acc, (_, _, r)| {
@@ -164,180 +342,188 @@ impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: Policy<A>, E: Env<S, A>> Agent
                 Some(*acc)
             })
             .collect();
+        R.reverse();
 
         // Forward update for value function
-        info.iter()
-            .zip(R)
-            .enumerate()
-            .for_each(|(t, ((s, a, _), r))| {
-                let v = self.get_action_value(s, a);
-                let alpha = 1.0 / (t + 1) as f64;
-                self.update_value(s, a, v + alpha * (r - v));
-            })
+        info.iter().zip(R).for_each(|((s, a, _), r)| {
+            let n = self.visit_count.entry((*s, *a)).or_insert(0);
+            *n += 1;
+            let alpha = self.step_size.alpha(*n);
+            let v = self.get_action_value(s, a);
+            self.update_value(s, a, v + alpha * (r - v));
+        })
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Trainable<S, A, P, E>
+    for QEveryVisitMC<S, A, P, E>
+{
+    fn learning(&self) -> Learning {
+        Learning::PerEpisode
+    }
+
+    fn information(&self, episode: &[Transition<S, A>], _policy: &P) -> Self::Information {
+        episode.iter().map(|t| (t.state, t.action, t.reward)).collect()
     }
 }
 
 // ┌──────────────────────────────────────────────────────────┐
-//  Q-Learning - TD0
+//  Value Iteration - First Visit MC
 // └──────────────────────────────────────────────────────────┘
-pub struct QTD0<S, A, P: Policy<A>, E: Env<S, A>> {
-    pub q_table: HashMap<(S, A), f64>,
-    pub gamma: f64,
-    t: usize,
-    c: f64,
-    eta: f64,
+pub struct VFirstVisitMC<S, A, P: StatePolicy<S, A>, E: Env<S, A>> {
+    pub value_function: HashMap<S, f64>,
+    pub visit_count: HashMap<S, usize>,
+    step_size: StepSize,
+    gamma: f64,
     _action_type: std::marker::PhantomData<A>,
     _policy_type: std::marker::PhantomData<P>,
     _env_type: std::marker::PhantomData<E>,
 }
 
-impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: Policy<A>, E: Env<S, A>> QTD0<S, A, P, E> {
-    pub fn new(gamma: f64, c: f64, eta: f64) -> Self {
+impl<S: Hash + Eq + Copy, A: Clone, P: StatePolicy<S, A>, E: Env<S, A>> VFirstVisitMC<S, A, P, E> {
+    pub fn new(gamma: f64) -> Self {
         Self {
-            q_table: HashMap::new(),
+            value_function: HashMap::new(),
+            visit_count: HashMap::new(),
+            step_size: StepSize::SampleAverage,
             gamma,
-            t: 0,
-            c,
-            eta,
             _action_type: std::marker::PhantomData,
             _policy_type: std::marker::PhantomData,
             _env_type: std::marker::PhantomData,
         }
     }
 
-    pub fn update_value(&mut self, state: &S, action: &A, value: f64) {
-        self.q_table.insert((*state, *action), value);
-    }
-
-    pub fn add_value(&mut self, state: &S, action: &A, value: f64) {
-        let old = *self.q_table.get(&(*state, *action)).unwrap_or(&0.0);
-        self.q_table.insert((*state, *action), old + value);
-    }
-
-    pub fn increment_count(&mut self) {
-        self.t += 1;
+    pub fn with_step_size(mut self, step_size: StepSize) -> Self {
+        self.step_size = step_size;
+        self
     }
 
-    pub fn reset_count(&mut self) {
-        self.t = 0;
+    pub fn reset_visit_count(&mut self) {
+        self.visit_count.clear();
     }
 
-    pub fn get_alpha(&self) -> f64 {
-        self.c * (self.t as f64 + 1f64).powf(-self.eta)
+    pub fn update_value(&mut self, state: &S, value: f64) {
+        self.value_function.insert(*state, value);
     }
 }
 
-impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: Policy<A>, E: Env<S, A>> Agent<S, A, P, E>
-    for QTD0<S, A, P, E>
+impl<S: Hash + Eq + Copy, A: Clone, P: StatePolicy<S, A>, E: Env<S, A>> Agent<S, A, P, E>
+    for VFirstVisitMC<S, A, P, E>
 {
-    // Information = Step
-    type Information = (S, A, f64, Option<S>, Vec<A>);
+    // Information = Episode
+    type Information = Vec<(S, f64)>;
 
-    fn get_action_value(&self, state: &S, action: &A) -> f64 {
-        *self.q_table.get(&(*state, *action)).unwrap_or(&0.0)
+    fn get_action_value(&self, _state: &S, _action: &A) -> f64 {
+        unimplemented!()
     }
 
-    fn get_value(&self, _state: &S) -> f64 {
-        unimplemented!()
+    fn get_value(&self, state: &S) -> f64 {
+        *self.value_function.get(state).unwrap_or(&0.0)
     }
 
     fn select_action(&self, state: &S, policy: &mut P, env: &E) -> Option<A> {
-        let actions = env.available_actions(state);
-        let candidates = actions
-            .iter()
-            .map(|a| (*a, self.get_action_value(state, a)))
-            .collect::<Vec<_>>();
-
-        policy.select_action(&candidates)
+        let candidates = lookahead_candidates(state, env, |s| self.get_value(s));
+        policy.select_action_at(state, &candidates)
     }
 
     #[allow(non_snake_case)]
     fn update(&mut self, info: &Self::Information) {
-        let (s, a, r, s_next, a_pool) = info;
+        if info.is_empty() {
+            panic!("Empty episode!")
+        }
 
-        let delta = if let Some(s_next) = s_next {
-            let mut Q_next_max = std::f64::MIN;
-            let mut a_next = vec![];
-            for a_prime in a_pool {
-                let q = self.get_action_value(s_next, a_prime);
-                if q > Q_next_max {
-                    Q_next_max = q;
-                    a_next = vec![*a_prime];
-                } else if q == Q_next_max {
-                    a_next.push(*a_prime);
-                }
+        // Backward update for cumulative discounted return
+        let mut R: Vec<f64> = info
+            .iter()
+            .rev()
+            .scan(0.0, |acc, (_, r)| {
+                *acc = *acc * self.gamma + r;
+                Some(*acc)
+            })
+            .collect();
+        R.reverse();
+
+        // Only the first occurrence of each state in the episode is used
+        let mut visited = HashSet::new();
+        info.iter().zip(R).for_each(|((s, _), r)| {
+            if visited.insert(*s) {
+                let n = self.visit_count.entry(*s).or_insert(0);
+                *n += 1;
+                let alpha = self.step_size.alpha(*n);
+                let v = self.get_value(s);
+                self.update_value(s, v + alpha * (r - v));
             }
-            let a_next = a_next.choose(&mut thread_rng()).unwrap();
-            r + self.gamma * self.get_action_value(s_next, a_next)
-        } else {
-            r - self.get_action_value(s, a)
-        };
+        });
+    }
+}
 
-        let alpha = self.get_alpha();
-        self.add_value(s, a, delta * alpha);
-        self.increment_count();
+impl<S: Hash + Eq + Copy, A: Clone, P: StatePolicy<S, A>, E: Env<S, A>> Trainable<S, A, P, E>
+    for VFirstVisitMC<S, A, P, E>
+{
+    fn learning(&self) -> Learning {
+        Learning::PerEpisode
+    }
+
+    fn information(&self, episode: &[Transition<S, A>], _policy: &P) -> Self::Information {
+        episode.iter().map(|t| (t.state, t.reward)).collect()
     }
 }
 
-pub struct QTD0Min<S, A, P: Policy<A>, E: Env<S, A>> {
+// ┌──────────────────────────────────────────────────────────┐
+//  Q-Learning - First Visit MC
+// └──────────────────────────────────────────────────────────┘
+pub struct QFirstVisitMC<S, A, P: StatePolicy<S, A>, E: Env<S, A>> {
     pub q_table: HashMap<(S, A), f64>,
-    pub gamma: f64,
-    t: usize,
-    c: f64,
-    eta: f64,
+    pub visit_count: HashMap<(S, A), usize>,
+    step_size: StepSize,
+    gamma: f64,
     _action_type: std::marker::PhantomData<A>,
     _policy_type: std::marker::PhantomData<P>,
     _env_type: std::marker::PhantomData<E>,
 }
 
-impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: Policy<A>, E: Env<S, A>> QTD0Min<S, A, P, E> {
-    pub fn new(gamma: f64, c: f64, eta: f64) -> Self {
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>>
+    QFirstVisitMC<S, A, P, E>
+{
+    pub fn new(gamma: f64) -> Self {
         Self {
             q_table: HashMap::new(),
+            visit_count: HashMap::new(),
+            step_size: StepSize::SampleAverage,
             gamma,
-            t: 0,
-            c,
-            eta,
             _action_type: std::marker::PhantomData,
             _policy_type: std::marker::PhantomData,
             _env_type: std::marker::PhantomData,
         }
     }
 
-    pub fn update_value(&mut self, state: &S, action: &A, value: f64) {
-        self.q_table.insert((*state, *action), value);
-    }
-
-    pub fn add_value(&mut self, state: &S, action: &A, value: f64) {
-        let old = *self.q_table.get(&(*state, *action)).unwrap_or(&0.0);
-        self.q_table.insert((*state, *action), old + value);
-    }
-
-    pub fn increment_count(&mut self) {
-        self.t += 1;
+    pub fn with_step_size(mut self, step_size: StepSize) -> Self {
+        self.step_size = step_size;
+        self
     }
 
-    pub fn reset_count(&mut self) {
-        self.t = 0;
+    pub fn reset_visit_count(&mut self) {
+        self.visit_count.clear();
     }
 
-    pub fn get_alpha(&self) -> f64 {
-        self.c * (self.t as f64 + 1f64).powf(-self.eta)
+    pub fn update_value(&mut self, state: &S, action: &A, value: f64) {
+        self.q_table.insert((*state, *action), value);
     }
 }
 
-impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: Policy<A>, E: Env<S, A>> Agent<S, A, P, E>
-    for QTD0Min<S, A, P, E>
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Agent<S, A, P, E>
+    for QFirstVisitMC<S, A, P, E>
 {
-    // Information = Step
-    type Information = (S, A, f64, Option<S>, Vec<A>);
+    // Information = Episode
+    type Information = Vec<(S, A, f64)>;
 
     fn get_action_value(&self, state: &S, action: &A) -> f64 {
         *self.q_table.get(&(*state, *action)).unwrap_or(&0.0)
     }
 
-    fn get_value(&self, _state: &S) -> f64 {
-        unimplemented!()
+    /// max_a Q(state, a) over the actions recorded for `state` (zero if there are none)
+    fn get_value(&self, state: &S) -> f64 {
+        best_action_value(&self.q_table, state, f64::max)
     }
 
     fn select_action(&self, state: &S, policy: &mut P, env: &E) -> Option<A> {
@@ -347,33 +533,2269 @@ impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: Policy<A>, E: Env<S, A>> Agent
             .map(|a| (*a, self.get_action_value(state, a)))
             .collect::<Vec<_>>();
 
-        policy.select_action(&candidates)
+        policy.select_action_at(state, &candidates)
     }
 
     #[allow(non_snake_case)]
     fn update(&mut self, info: &Self::Information) {
-        let (s, a, r, s_next, a_pool) = info;
+        if info.is_empty() {
+            panic!("Empty episode!")
+        }
 
-        let delta = if let Some(s_next) = s_next {
-            let mut Q_next_min = std::f64::MAX;
-            let mut a_next = vec![];
-            for a_prime in a_pool {
-                let q = self.get_action_value(s_next, a_prime);
-                if q < Q_next_min {
-                    Q_next_min = q;
-                    a_next = vec![*a_prime];
-                } else if q == Q_next_min {
-                    a_next.push(*a_prime);
-                }
+        // Backward update for cumulative discounted return
+        let mut R: Vec<f64> = info
+            .iter()
+            .rev()
+            .scan(0.0, |acc, (_, _, r)| {
+                *acc = *acc * self.gamma + r;
+                Some(*acc)
+            })
+            .collect();
+        R.reverse();
+
+        // Only the first occurrence of each state-action pair in the episode is used
+        let mut visited = HashSet::new();
+        info.iter().zip(R).for_each(|((s, a, _), r)| {
+            if visited.insert((*s, *a)) {
+                let n = self.visit_count.entry((*s, *a)).or_insert(0);
+                *n += 1;
+                let alpha = self.step_size.alpha(*n);
+                let q = self.get_action_value(s, a);
+                self.update_value(s, a, q + alpha * (r - q));
             }
-            let a_next = a_next.choose(&mut thread_rng()).unwrap();
-            r + self.gamma * self.get_action_value(s_next, a_next)
-        } else {
-            r - self.get_action_value(s, a)
-        };
+        });
+    }
+}
 
-        let alpha = self.get_alpha();
-        self.add_value(s, a, delta * alpha);
-        self.increment_count();
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Trainable<S, A, P, E>
+    for QFirstVisitMC<S, A, P, E>
+{
+    fn learning(&self) -> Learning {
+        Learning::PerEpisode
+    }
+
+    fn information(&self, episode: &[Transition<S, A>], _policy: &P) -> Self::Information {
+        episode.iter().map(|t| (t.state, t.action, t.reward)).collect()
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Monte Carlo Exploring Starts
+//  - First visit MC control with a greedy policy (ties are broken by action order)
+//  - Exploration comes only from random starts (`Trainer::train_exploring_starts`)
+//  - `P` is never consulted; it only satisfies the `Trainer`, so any policy (e.g. ε = 0) will do
+//  - The greedy policy can cycle: episodes must be truncated with `Episodic::with_max_steps`
+// └──────────────────────────────────────────────────────────┘
+pub struct MCES<S, A, P: StatePolicy<S, A>, E: Env<S, A>> {
+    pub q_table: HashMap<(S, A), f64>,
+    pub visit_count: HashMap<(S, A), usize>,
+    step_size: StepSize,
+    gamma: f64,
+    _action_type: std::marker::PhantomData<A>,
+    _policy_type: std::marker::PhantomData<P>,
+    _env_type: std::marker::PhantomData<E>,
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> MCES<S, A, P, E> {
+    pub fn new(gamma: f64) -> Self {
+        Self {
+            q_table: HashMap::new(),
+            visit_count: HashMap::new(),
+            step_size: StepSize::SampleAverage,
+            gamma,
+            _action_type: std::marker::PhantomData,
+            _policy_type: std::marker::PhantomData,
+            _env_type: std::marker::PhantomData,
+        }
+    }
+
+    pub fn with_step_size(mut self, step_size: StepSize) -> Self {
+        self.step_size = step_size;
+        self
+    }
+
+    pub fn reset_visit_count(&mut self) {
+        self.visit_count.clear();
+    }
+
+    pub fn update_value(&mut self, state: &S, action: &A, value: f64) {
+        self.q_table.insert((*state, *action), value);
+    }
+
+    /// Greedy action of `state` (ties are broken by action order)
+    pub fn greedy_action(&self, state: &S, env: &E) -> Option<A> {
+        let mut best: Option<(A, f64)> = None;
+        for a in env.available_actions(state) {
+            let q = *self.q_table.get(&(*state, a)).unwrap_or(&0.0);
+            if best.as_ref().is_none_or(|(_, q_best)| q > *q_best) {
+                best = Some((a, q));
+            }
+        }
+        best.map(|(a, _)| a)
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Agent<S, A, P, E>
+    for MCES<S, A, P, E>
+{
+    // Information = Episode
+    type Information = Vec<(S, A, f64)>;
+
+    fn get_action_value(&self, state: &S, action: &A) -> f64 {
+        *self.q_table.get(&(*state, *action)).unwrap_or(&0.0)
+    }
+
+    /// max_a Q(state, a) over the actions recorded for `state` (zero if there are none)
+    fn get_value(&self, state: &S) -> f64 {
+        best_action_value(&self.q_table, state, f64::max)
+    }
+
+    /// The policy is ignored: MC-ES always acts greedily
+    fn select_action(&self, state: &S, _policy: &mut P, env: &E) -> Option<A> {
+        self.greedy_action(state, env)
+    }
+
+    #[allow(non_snake_case)]
+    fn update(&mut self, info: &Self::Information) {
+        if info.is_empty() {
+            panic!("Empty episode!")
+        }
+
+        // Backward update for cumulative discounted return
+        let mut R: Vec<f64> = info
+            .iter()
+            .rev()
+            .scan(0.0, |acc, (_, _, r)| {
+                *acc = *acc * self.gamma + r;
+                Some(*acc)
+            })
+            .collect();
+        R.reverse();
+
+        let mut visited = HashSet::new();
+        info.iter().zip(R).for_each(|((s, a, _), r)| {
+            if visited.insert((*s, *a)) {
+                let n = self.visit_count.entry((*s, *a)).or_insert(0);
+                *n += 1;
+                let alpha = self.step_size.alpha(*n);
+                let q = self.get_action_value(s, a);
+                self.update_value(s, a, q + alpha * (r - q));
+            }
+        });
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Trainable<S, A, P, E>
+    for MCES<S, A, P, E>
+{
+    fn learning(&self) -> Learning {
+        Learning::PerEpisode
+    }
+
+    fn information(&self, episode: &[Transition<S, A>], _policy: &P) -> Self::Information {
+        episode.iter().map(|t| (t.state, t.action, t.reward)).collect()
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Off-policy Monte Carlo
+//  - Learns about a target policy π from episodes of a behavior policy b (the `Policy` P)
+//  - Returns are weighted by the importance sampling ratio W = Π π(a|s) / b(a|s)
+// └──────────────────────────────────────────────────────────┘
+/// Importance sampling estimator
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Sampling {
+    /// Q(s, a) = Σ W G / N(s, a) (unbiased, unbounded variance)
+    Ordinary,
+    /// Q(s, a) = Σ W G / Σ W (biased, bounded variance)
+    Weighted,
+}
+
+/// Target policy probability π(a|s)
+pub type TargetProbability<S, A> = Box<dyn Fn(&S, &A) -> f64>;
+
+pub struct OffPolicyMCPrediction<S, A, P: StatePolicy<S, A>, E: Env<S, A>> {
+    pub q_table: HashMap<(S, A), f64>,
+    pub visit_count: HashMap<(S, A), usize>,
+    pub cumulative_weight: HashMap<(S, A), f64>,
+    sampling: Sampling,
+    gamma: f64,
+    target: TargetProbability<S, A>,
+    _action_type: std::marker::PhantomData<A>,
+    _policy_type: std::marker::PhantomData<P>,
+    _env_type: std::marker::PhantomData<E>,
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>>
+    OffPolicyMCPrediction<S, A, P, E>
+{
+    /// `target` gives the probability π(a|s) of the policy to evaluate
+    pub fn new(gamma: f64, target: impl Fn(&S, &A) -> f64 + 'static) -> Self {
+        Self {
+            q_table: HashMap::new(),
+            visit_count: HashMap::new(),
+            cumulative_weight: HashMap::new(),
+            sampling: Sampling::Weighted,
+            gamma,
+            target: Box::new(target),
+            _action_type: std::marker::PhantomData,
+            _policy_type: std::marker::PhantomData,
+            _env_type: std::marker::PhantomData,
+        }
+    }
+
+    pub fn with_sampling(mut self, sampling: Sampling) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    pub fn target_probability(&self, state: &S, action: &A) -> f64 {
+        (self.target)(state, action)
+    }
+
+    pub fn update_value(&mut self, state: &S, action: &A, value: f64) {
+        self.q_table.insert((*state, *action), value);
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Agent<S, A, P, E>
+    for OffPolicyMCPrediction<S, A, P, E>
+{
+    // Information = Episode of (s, a, r, π(a|s), b(a|s))
+    type Information = Vec<(S, A, f64, f64, f64)>;
+
+    fn get_action_value(&self, state: &S, action: &A) -> f64 {
+        *self.q_table.get(&(*state, *action)).unwrap_or(&0.0)
+    }
+
+    /// Σ_a π(a|state) Q(state, a) over the actions recorded for `state`
+    fn get_value(&self, state: &S) -> f64 {
+        self.q_table
+            .iter()
+            .filter(|((s, _), _)| s == state)
+            .map(|((s, a), q)| self.target_probability(s, a) * q)
+            .sum()
+    }
+
+    fn select_action(&self, state: &S, policy: &mut P, env: &E) -> Option<A> {
+        let actions = env.available_actions(state);
+        let candidates = actions
+            .iter()
+            .map(|a| (*a, self.get_action_value(state, a)))
+            .collect::<Vec<_>>();
+
+        policy.select_action_at(state, &candidates)
+    }
+
+    #[allow(non_snake_case)]
+    fn update(&mut self, info: &Self::Information) {
+        if info.is_empty() {
+            panic!("Empty episode!")
+        }
+
+        // Backward update: W weights the return of (s, a) by the ratios of the later steps
+        let mut G = 0f64;
+        let mut W = 1f64;
+        for (s, a, r, pi, b) in info.iter().rev() {
+            assert!(*b > 0f64, "Behavior policy must cover the taken action!");
+            G = self.gamma * G + r;
+
+            let q = self.get_action_value(s, a);
+            match self.sampling {
+                Sampling::Ordinary => {
+                    let n = self.visit_count.entry((*s, *a)).or_insert(0);
+                    *n += 1;
+                    let n = *n as f64;
+                    self.update_value(s, a, q + (W * G - q) / n);
+                }
+                Sampling::Weighted => {
+                    let c = self.cumulative_weight.entry((*s, *a)).or_insert(0f64);
+                    *c += W;
+                    let c = *c;
+                    self.update_value(s, a, q + W / c * (G - q));
+                }
+            }
+
+            W *= pi / b;
+            // Weighted estimates are unchanged by zero weights, ordinary ones still count them
+            if W == 0f64 && self.sampling == Sampling::Weighted {
+                break;
+            }
+        }
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Trainable<S, A, P, E>
+    for OffPolicyMCPrediction<S, A, P, E>
+{
+    fn learning(&self) -> Learning {
+        Learning::PerEpisode
+    }
+
+    /// Behavior probabilities are those of `policy` in each visited state, over the current action values
+    fn information(&self, episode: &[Transition<S, A>], policy: &P) -> Self::Information {
+        episode
+            .iter()
+            .map(|t| {
+                let candidates = t
+                    .actions
+                    .iter()
+                    .map(|a| (*a, self.get_action_value(&t.state, a)))
+                    .collect::<Vec<_>>();
+                let b = policy.action_probability_at(&t.state, &candidates, &t.action);
+                let pi = self.target_probability(&t.state, &t.action);
+                (t.state, t.action, t.reward, pi, b)
+            })
+            .collect()
+    }
+}
+
+pub struct OffPolicyMCControl<S, A, P: StatePolicy<S, A>, E: Env<S, A>> {
+    pub q_table: HashMap<(S, A), f64>,
+    pub visit_count: HashMap<(S, A), usize>,
+    pub cumulative_weight: HashMap<(S, A), f64>,
+    sampling: Sampling,
+    gamma: f64,
+    _action_type: std::marker::PhantomData<A>,
+    _policy_type: std::marker::PhantomData<P>,
+    _env_type: std::marker::PhantomData<E>,
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>>
+    OffPolicyMCControl<S, A, P, E>
+{
+    pub fn new(gamma: f64) -> Self {
+        Self {
+            q_table: HashMap::new(),
+            visit_count: HashMap::new(),
+            cumulative_weight: HashMap::new(),
+            sampling: Sampling::Weighted,
+            gamma,
+            _action_type: std::marker::PhantomData,
+            _policy_type: std::marker::PhantomData,
+            _env_type: std::marker::PhantomData,
+        }
+    }
+
+    pub fn with_sampling(mut self, sampling: Sampling) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    /// Probability of `action` under the greedy target policy (ties share the probability)
+    pub fn target_probability(&self, state: &S, action: &A, actions: &[A]) -> f64 {
+        let q_values = actions
+            .iter()
+            .map(|a| *self.q_table.get(&(*state, *a)).unwrap_or(&0.0))
+            .collect::<Vec<_>>();
+        let q_max = q_values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let q = *self.q_table.get(&(*state, *action)).unwrap_or(&0.0);
+        if q < q_max {
+            0f64
+        } else {
+            1f64 / q_values.iter().filter(|q| **q == q_max).count() as f64
+        }
+    }
+
+    pub fn update_value(&mut self, state: &S, action: &A, value: f64) {
+        self.q_table.insert((*state, *action), value);
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Agent<S, A, P, E>
+    for OffPolicyMCControl<S, A, P, E>
+{
+    // Information = Episode of (s, a, r, b(a|s), actions available in s)
+    type Information = Vec<(S, A, f64, f64, Vec<A>)>;
+
+    fn get_action_value(&self, state: &S, action: &A) -> f64 {
+        *self.q_table.get(&(*state, *action)).unwrap_or(&0.0)
+    }
+
+    /// max_a Q(state, a) over the actions recorded for `state` (zero if there are none)
+    fn get_value(&self, state: &S) -> f64 {
+        best_action_value(&self.q_table, state, f64::max)
+    }
+
+    fn select_action(&self, state: &S, policy: &mut P, env: &E) -> Option<A> {
+        let actions = env.available_actions(state);
+        let candidates = actions
+            .iter()
+            .map(|a| (*a, self.get_action_value(state, a)))
+            .collect::<Vec<_>>();
+
+        policy.select_action_at(state, &candidates)
+    }
+
+    #[allow(non_snake_case)]
+    fn update(&mut self, info: &Self::Information) {
+        if info.is_empty() {
+            panic!("Empty episode!")
+        }
+
+        let mut G = 0f64;
+        let mut W = 1f64;
+        for (s, a, r, b, actions) in info.iter().rev() {
+            assert!(*b > 0f64, "Behavior policy must cover the taken action!");
+            G = self.gamma * G + r;
+
+            let q = self.get_action_value(s, a);
+            match self.sampling {
+                Sampling::Ordinary => {
+                    let n = self.visit_count.entry((*s, *a)).or_insert(0);
+                    *n += 1;
+                    let n = *n as f64;
+                    self.update_value(s, a, q + (W * G - q) / n);
+                }
+                Sampling::Weighted => {
+                    let c = self.cumulative_weight.entry((*s, *a)).or_insert(0f64);
+                    *c += W;
+                    let c = *c;
+                    self.update_value(s, a, q + W / c * (G - q));
+                }
+            }
+
+            // The target is greedy with respect to the updated values
+            W *= self.target_probability(s, a, actions) / b;
+            if W == 0f64 && self.sampling == Sampling::Weighted {
+                break;
+            }
+        }
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Trainable<S, A, P, E>
+    for OffPolicyMCControl<S, A, P, E>
+{
+    fn learning(&self) -> Learning {
+        Learning::PerEpisode
+    }
+
+    /// Behavior probabilities are those of `policy` in each visited state, over the current action values
+    fn information(&self, episode: &[Transition<S, A>], policy: &P) -> Self::Information {
+        episode
+            .iter()
+            .map(|t| {
+                let candidates = t
+                    .actions
+                    .iter()
+                    .map(|a| (*a, self.get_action_value(&t.state, a)))
+                    .collect::<Vec<_>>();
+                let b = policy.action_probability_at(&t.state, &candidates, &t.action);
+                (t.state, t.action, t.reward, b, t.actions.clone())
+            })
+            .collect()
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Q-Learning - TD0
+// └──────────────────────────────────────────────────────────┘
+pub struct QTD0<S, A, P: StatePolicy<S, A>, E: Env<S, A>> {
+    pub q_table: QTable<S, A>,
+    pub gamma: f64,
+    rng: StdRng,
+    _action_type: std::marker::PhantomData<A>,
+    _policy_type: std::marker::PhantomData<P>,
+    _env_type: std::marker::PhantomData<E>,
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> QTD0<S, A, P, E> {
+    pub fn new(gamma: f64, c: f64, eta: f64) -> Self {
+        Self {
+            q_table: QTable::new(c, eta),
+            gamma,
+            rng: StdRng::from_entropy(),
+            _action_type: std::marker::PhantomData,
+            _policy_type: std::marker::PhantomData,
+            _env_type: std::marker::PhantomData,
+        }
+    }
+
+    /// Seed the RNG used for tie-breaking in the bootstrap target
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    pub fn with_rng(mut self, rng: StdRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    pub fn update_value(&mut self, state: &S, action: &A, value: f64) {
+        self.q_table.update_value((*state, *action), value);
+    }
+
+    pub fn add_value(&mut self, state: &S, action: &A, value: f64) {
+        self.q_table.add_value((*state, *action), value);
+    }
+
+    pub fn increment_count(&mut self) {
+        self.q_table.increment_count();
+    }
+
+    pub fn reset_count(&mut self) {
+        self.q_table.reset_count();
+    }
+
+    pub fn get_alpha(&self) -> f64 {
+        self.q_table.get_alpha()
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Agent<S, A, P, E>
+    for QTD0<S, A, P, E>
+{
+    // Information = Step
+    type Information = (S, A, f64, Option<S>, Vec<A>);
+
+    fn get_action_value(&self, state: &S, action: &A) -> f64 {
+        self.q_table.value(&(*state, *action))
+    }
+
+    fn get_value(&self, state: &S) -> f64 {
+        self.q_table.max_value(state)
+    }
+
+    fn select_action(&self, state: &S, policy: &mut P, env: &E) -> Option<A> {
+        let actions = env.available_actions(state);
+        let candidates = actions
+            .iter()
+            .map(|a| (*a, self.get_action_value(state, a)))
+            .collect::<Vec<_>>();
+
+        policy.select_action_at(state, &candidates)
+    }
+
+    #[allow(non_snake_case)]
+    fn update(&mut self, info: &Self::Information) {
+        let (s, a, r, s_next, a_pool) = info;
+
+        let delta = match s_next {
+            Some(s_next) if !a_pool.is_empty() => {
+                let mut Q_next_max = f64::MIN;
+                let mut a_next = vec![];
+                for a_prime in a_pool {
+                    let q = self.get_action_value(s_next, a_prime);
+                    if q > Q_next_max {
+                        Q_next_max = q;
+                        a_next = vec![*a_prime];
+                    } else if q == Q_next_max {
+                        a_next.push(*a_prime);
+                    }
+                }
+                let a_next = a_next.choose(&mut self.rng).unwrap();
+                r + self.gamma * self.get_action_value(s_next, a_next) - self.get_action_value(s, a)
+            }
+            _ => r - self.get_action_value(s, a),
+        };
+
+        let alpha = self.get_alpha();
+        self.add_value(s, a, delta * alpha);
+        self.increment_count();
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Trainable<S, A, P, E>
+    for QTD0<S, A, P, E>
+{
+    fn learning(&self) -> Learning {
+        Learning::PerStep
+    }
+
+    fn information(&self, episode: &[Transition<S, A>], _policy: &P) -> Self::Information {
+        let t = episode.last().unwrap();
+        (t.state, t.action, t.reward, t.next_state, t.next_actions.clone())
+    }
+}
+
+pub struct QTD0Min<S, A, P: StatePolicy<S, A>, E: Env<S, A>> {
+    pub q_table: QTable<S, A>,
+    pub gamma: f64,
+    rng: StdRng,
+    _action_type: std::marker::PhantomData<A>,
+    _policy_type: std::marker::PhantomData<P>,
+    _env_type: std::marker::PhantomData<E>,
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> QTD0Min<S, A, P, E> {
+    pub fn new(gamma: f64, c: f64, eta: f64) -> Self {
+        Self {
+            q_table: QTable::new(c, eta),
+            gamma,
+            rng: StdRng::from_entropy(),
+            _action_type: std::marker::PhantomData,
+            _policy_type: std::marker::PhantomData,
+            _env_type: std::marker::PhantomData,
+        }
+    }
+
+    /// Seed the RNG used for tie-breaking in the bootstrap target
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    pub fn with_rng(mut self, rng: StdRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    pub fn update_value(&mut self, state: &S, action: &A, value: f64) {
+        self.q_table.update_value((*state, *action), value);
+    }
+
+    pub fn add_value(&mut self, state: &S, action: &A, value: f64) {
+        self.q_table.add_value((*state, *action), value);
+    }
+
+    pub fn increment_count(&mut self) {
+        self.q_table.increment_count();
+    }
+
+    pub fn reset_count(&mut self) {
+        self.q_table.reset_count();
+    }
+
+    pub fn get_alpha(&self) -> f64 {
+        self.q_table.get_alpha()
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Agent<S, A, P, E>
+    for QTD0Min<S, A, P, E>
+{
+    // Information = Step
+    type Information = (S, A, f64, Option<S>, Vec<A>);
+
+    fn get_action_value(&self, state: &S, action: &A) -> f64 {
+        self.q_table.value(&(*state, *action))
+    }
+
+    fn get_value(&self, state: &S) -> f64 {
+        self.q_table.min_value(state)
+    }
+
+    fn select_action(&self, state: &S, policy: &mut P, env: &E) -> Option<A> {
+        let actions = env.available_actions(state);
+        let candidates = actions
+            .iter()
+            .map(|a| (*a, self.get_action_value(state, a)))
+            .collect::<Vec<_>>();
+
+        policy.select_action_at(state, &candidates)
+    }
+
+    #[allow(non_snake_case)]
+    fn update(&mut self, info: &Self::Information) {
+        let (s, a, r, s_next, a_pool) = info;
+
+        let delta = match s_next {
+            Some(s_next) if !a_pool.is_empty() => {
+                let mut Q_next_min = f64::MAX;
+                let mut a_next = vec![];
+                for a_prime in a_pool {
+                    let q = self.get_action_value(s_next, a_prime);
+                    if q < Q_next_min {
+                        Q_next_min = q;
+                        a_next = vec![*a_prime];
+                    } else if q == Q_next_min {
+                        a_next.push(*a_prime);
+                    }
+                }
+                let a_next = a_next.choose(&mut self.rng).unwrap();
+                r + self.gamma * self.get_action_value(s_next, a_next) - self.get_action_value(s, a)
+            }
+            _ => r - self.get_action_value(s, a),
+        };
+
+        let alpha = self.get_alpha();
+        self.add_value(s, a, delta * alpha);
+        self.increment_count();
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Trainable<S, A, P, E>
+    for QTD0Min<S, A, P, E>
+{
+    fn learning(&self) -> Learning {
+        Learning::PerStep
+    }
+
+    fn information(&self, episode: &[Transition<S, A>], _policy: &P) -> Self::Information {
+        let t = episode.last().unwrap();
+        (t.state, t.action, t.reward, t.next_state, t.next_actions.clone())
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  SARSA - On-policy TD0
+// └──────────────────────────────────────────────────────────┘
+pub struct SARSA<S, A, P: StatePolicy<S, A>, E: Env<S, A>> {
+    pub q_table: QTable<S, A>,
+    pub gamma: f64,
+    _action_type: std::marker::PhantomData<A>,
+    _policy_type: std::marker::PhantomData<P>,
+    _env_type: std::marker::PhantomData<E>,
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> SARSA<S, A, P, E> {
+    pub fn new(gamma: f64, c: f64, eta: f64) -> Self {
+        Self {
+            q_table: QTable::new(c, eta),
+            gamma,
+            _action_type: std::marker::PhantomData,
+            _policy_type: std::marker::PhantomData,
+            _env_type: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Agent<S, A, P, E>
+    for SARSA<S, A, P, E>
+{
+    // Information = Step (s, a, r, s', a')
+    type Information = (S, A, f64, Option<S>, Option<A>);
+
+    fn get_action_value(&self, state: &S, action: &A) -> f64 {
+        self.q_table.value(&(*state, *action))
+    }
+
+    fn get_value(&self, state: &S) -> f64 {
+        self.q_table.max_value(state)
+    }
+
+    fn select_action(&self, state: &S, policy: &mut P, env: &E) -> Option<A> {
+        let actions = env.available_actions(state);
+        let candidates = actions
+            .iter()
+            .map(|a| (*a, self.get_action_value(state, a)))
+            .collect::<Vec<_>>();
+
+        policy.select_action_at(state, &candidates)
+    }
+
+    fn update(&mut self, info: &Self::Information) {
+        let (s, a, r, s_next, a_next) = info;
+
+        // Bootstrap from the action actually taken in the next state
+        let target = match (s_next, a_next) {
+            (Some(s_next), Some(a_next)) => r + self.gamma * self.get_action_value(s_next, a_next),
+            _ => *r,
+        };
+        let delta = target - self.get_action_value(s, a);
+
+        let alpha = self.q_table.get_alpha();
+        self.q_table.add_value((*s, *a), delta * alpha);
+        self.q_table.increment_count();
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Trainable<S, A, P, E>
+    for SARSA<S, A, P, E>
+{
+    fn learning(&self) -> Learning {
+        Learning::PerStep
+    }
+
+    fn information(&self, episode: &[Transition<S, A>], _policy: &P) -> Self::Information {
+        let t = episode.last().unwrap();
+        (t.state, t.action, t.reward, t.next_state, t.next_action)
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Expected SARSA
+// └──────────────────────────────────────────────────────────┘
+pub struct ExpectedSARSA<S, A, P: StatePolicy<S, A>, E: Env<S, A>> {
+    pub q_table: QTable<S, A>,
+    pub gamma: f64,
+    _action_type: std::marker::PhantomData<A>,
+    _policy_type: std::marker::PhantomData<P>,
+    _env_type: std::marker::PhantomData<E>,
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> ExpectedSARSA<S, A, P, E> {
+    pub fn new(gamma: f64, c: f64, eta: f64) -> Self {
+        Self {
+            q_table: QTable::new(c, eta),
+            gamma,
+            _action_type: std::marker::PhantomData,
+            _policy_type: std::marker::PhantomData,
+            _env_type: std::marker::PhantomData,
+        }
+    }
+
+    /// `(action, probability)` pairs of the policy over `actions` in `state`
+    pub fn action_probabilities(&self, state: &S, actions: &[A], policy: &P) -> Vec<(A, f64)> {
+        let candidates = actions
+            .iter()
+            .map(|a| (*a, self.get_action_value(state, a)))
+            .collect::<Vec<_>>();
+        let probs = policy.action_probabilities_at(state, &candidates);
+
+        actions.iter().copied().zip(probs).collect()
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Agent<S, A, P, E>
+    for ExpectedSARSA<S, A, P, E>
+{
+    // Information = Step (s, a, r, s', [(a', π(a'|s'))])
+    type Information = (S, A, f64, Option<S>, Vec<(A, f64)>);
+
+    fn get_action_value(&self, state: &S, action: &A) -> f64 {
+        self.q_table.value(&(*state, *action))
+    }
+
+    fn get_value(&self, state: &S) -> f64 {
+        self.q_table.max_value(state)
+    }
+
+    fn select_action(&self, state: &S, policy: &mut P, env: &E) -> Option<A> {
+        let actions = env.available_actions(state);
+        let candidates = actions
+            .iter()
+            .map(|a| (*a, self.get_action_value(state, a)))
+            .collect::<Vec<_>>();
+
+        policy.select_action_at(state, &candidates)
+    }
+
+    fn update(&mut self, info: &Self::Information) {
+        let (s, a, r, s_next, a_probs) = info;
+
+        // Expectation of Q(s', ·) under the policy
+        let target = match s_next {
+            Some(s_next) => {
+                let expected = a_probs
+                    .iter()
+                    .map(|(a_prime, p)| p * self.get_action_value(s_next, a_prime))
+                    .sum::<f64>();
+                r + self.gamma * expected
+            }
+            None => *r,
+        };
+        let delta = target - self.get_action_value(s, a);
+
+        let alpha = self.q_table.get_alpha();
+        self.q_table.add_value((*s, *a), delta * alpha);
+        self.q_table.increment_count();
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Trainable<S, A, P, E>
+    for ExpectedSARSA<S, A, P, E>
+{
+    fn learning(&self) -> Learning {
+        Learning::PerStep
+    }
+
+    fn information(&self, episode: &[Transition<S, A>], policy: &P) -> Self::Information {
+        let t = episode.last().unwrap();
+        let a_probs = match t.next_state {
+            Some(s_next) => self.action_probabilities(&s_next, &t.next_actions, policy),
+            None => vec![],
+        };
+        (t.state, t.action, t.reward, t.next_state, a_probs)
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Double Q-Learning - TD0
+//  - Each table keeps its own step size schedule (α decays with the updates of that table)
+// └──────────────────────────────────────────────────────────┘
+pub struct DoubleQTD0<S, A, P: StatePolicy<S, A>, E: Env<S, A>> {
+    pub q_table_a: QTable<S, A>,
+    pub q_table_b: QTable<S, A>,
+    pub gamma: f64,
+    rng: StdRng,
+    _action_type: std::marker::PhantomData<A>,
+    _policy_type: std::marker::PhantomData<P>,
+    _env_type: std::marker::PhantomData<E>,
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> DoubleQTD0<S, A, P, E> {
+    pub fn new(gamma: f64, c: f64, eta: f64) -> Self {
+        Self {
+            q_table_a: QTable::new(c, eta),
+            q_table_b: QTable::new(c, eta),
+            gamma,
+            rng: StdRng::from_entropy(),
+            _action_type: std::marker::PhantomData,
+            _policy_type: std::marker::PhantomData,
+            _env_type: std::marker::PhantomData,
+        }
+    }
+
+    /// Seed the RNG used to pick the table to update and for tie-breaking
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    pub fn with_rng(mut self, rng: StdRng) -> Self {
+        self.rng = rng;
+        self
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Agent<S, A, P, E>
+    for DoubleQTD0<S, A, P, E>
+{
+    // Information = Step
+    type Information = (S, A, f64, Option<S>, Vec<A>);
+
+    /// Sum of both tables
+    fn get_action_value(&self, state: &S, action: &A) -> f64 {
+        self.q_table_a.value(&(*state, *action)) + self.q_table_b.value(&(*state, *action))
+    }
+
+    /// max_a Q(state, a) over the actions recorded in either table (zero if there are none)
+    fn get_value(&self, state: &S) -> f64 {
+        self.q_table_a
+            .keys()
+            .chain(self.q_table_b.keys())
+            .filter(|(s, _)| s == state)
+            .map(|(_, a)| self.get_action_value(state, a))
+            .reduce(f64::max)
+            .unwrap_or(0f64)
+    }
+
+    fn select_action(&self, state: &S, policy: &mut P, env: &E) -> Option<A> {
+        let actions = env.available_actions(state);
+        let candidates = actions
+            .iter()
+            .map(|a| (*a, self.get_action_value(state, a)))
+            .collect::<Vec<_>>();
+
+        policy.select_action_at(state, &candidates)
+    }
+
+    #[allow(non_snake_case)]
+    fn update(&mut self, info: &Self::Information) {
+        let (s, a, r, s_next, a_pool) = info;
+
+        // Select with one table, evaluate with the other
+        let (q_select, q_eval) = if self.rng.gen_bool(0.5) {
+            (&mut self.q_table_a, &self.q_table_b)
+        } else {
+            (&mut self.q_table_b, &self.q_table_a)
+        };
+
+        let target = match s_next {
+            Some(s_next) if !a_pool.is_empty() => {
+                let mut Q_next_max = f64::MIN;
+                let mut a_next = vec![];
+                for a_prime in a_pool {
+                    let q = q_select.value(&(*s_next, *a_prime));
+                    if q > Q_next_max {
+                        Q_next_max = q;
+                        a_next = vec![*a_prime];
+                    } else if q == Q_next_max {
+                        a_next.push(*a_prime);
+                    }
+                }
+                let a_next = a_next.choose(&mut self.rng).unwrap();
+                r + self.gamma * q_eval.value(&(*s_next, *a_next))
+            }
+            _ => *r,
+        };
+
+        let alpha = q_select.get_alpha();
+        let q = q_select.value(&(*s, *a));
+        q_select.add_value((*s, *a), alpha * (target - q));
+        q_select.increment_count();
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Trainable<S, A, P, E>
+    for DoubleQTD0<S, A, P, E>
+{
+    fn learning(&self) -> Learning {
+        Learning::PerStep
+    }
+
+    fn information(&self, episode: &[Transition<S, A>], _policy: &P) -> Self::Information {
+        let t = episode.last().unwrap();
+        (t.state, t.action, t.reward, t.next_state, t.next_actions.clone())
+    }
+}
+
+pub struct DoubleQTD0Min<S, A, P: StatePolicy<S, A>, E: Env<S, A>> {
+    pub q_table_a: QTable<S, A>,
+    pub q_table_b: QTable<S, A>,
+    pub gamma: f64,
+    rng: StdRng,
+    _action_type: std::marker::PhantomData<A>,
+    _policy_type: std::marker::PhantomData<P>,
+    _env_type: std::marker::PhantomData<E>,
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> DoubleQTD0Min<S, A, P, E> {
+    pub fn new(gamma: f64, c: f64, eta: f64) -> Self {
+        Self {
+            q_table_a: QTable::new(c, eta),
+            q_table_b: QTable::new(c, eta),
+            gamma,
+            rng: StdRng::from_entropy(),
+            _action_type: std::marker::PhantomData,
+            _policy_type: std::marker::PhantomData,
+            _env_type: std::marker::PhantomData,
+        }
+    }
+
+    /// Seed the RNG used to pick the table to update and for tie-breaking
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    pub fn with_rng(mut self, rng: StdRng) -> Self {
+        self.rng = rng;
+        self
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Agent<S, A, P, E>
+    for DoubleQTD0Min<S, A, P, E>
+{
+    // Information = Step
+    type Information = (S, A, f64, Option<S>, Vec<A>);
+
+    /// Sum of both tables
+    fn get_action_value(&self, state: &S, action: &A) -> f64 {
+        self.q_table_a.value(&(*state, *action)) + self.q_table_b.value(&(*state, *action))
+    }
+
+    /// min_a Q(state, a) over the actions recorded in either table (zero if there are none)
+    fn get_value(&self, state: &S) -> f64 {
+        self.q_table_a
+            .keys()
+            .chain(self.q_table_b.keys())
+            .filter(|(s, _)| s == state)
+            .map(|(_, a)| self.get_action_value(state, a))
+            .reduce(f64::min)
+            .unwrap_or(0f64)
+    }
+
+    fn select_action(&self, state: &S, policy: &mut P, env: &E) -> Option<A> {
+        let actions = env.available_actions(state);
+        let candidates = actions
+            .iter()
+            .map(|a| (*a, self.get_action_value(state, a)))
+            .collect::<Vec<_>>();
+
+        policy.select_action_at(state, &candidates)
+    }
+
+    #[allow(non_snake_case)]
+    fn update(&mut self, info: &Self::Information) {
+        let (s, a, r, s_next, a_pool) = info;
+
+        // Select with one table, evaluate with the other
+        let (q_select, q_eval) = if self.rng.gen_bool(0.5) {
+            (&mut self.q_table_a, &self.q_table_b)
+        } else {
+            (&mut self.q_table_b, &self.q_table_a)
+        };
+
+        let target = match s_next {
+            Some(s_next) if !a_pool.is_empty() => {
+                let mut Q_next_min = f64::MAX;
+                let mut a_next = vec![];
+                for a_prime in a_pool {
+                    let q = q_select.value(&(*s_next, *a_prime));
+                    if q < Q_next_min {
+                        Q_next_min = q;
+                        a_next = vec![*a_prime];
+                    } else if q == Q_next_min {
+                        a_next.push(*a_prime);
+                    }
+                }
+                let a_next = a_next.choose(&mut self.rng).unwrap();
+                r + self.gamma * q_eval.value(&(*s_next, *a_next))
+            }
+            _ => *r,
+        };
+
+        let alpha = q_select.get_alpha();
+        let q = q_select.value(&(*s, *a));
+        q_select.add_value((*s, *a), alpha * (target - q));
+        q_select.increment_count();
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Trainable<S, A, P, E>
+    for DoubleQTD0Min<S, A, P, E>
+{
+    fn learning(&self) -> Learning {
+        Learning::PerStep
+    }
+
+    fn information(&self, episode: &[Transition<S, A>], _policy: &P) -> Self::Information {
+        let t = episode.last().unwrap();
+        (t.state, t.action, t.reward, t.next_state, t.next_actions.clone())
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  n-step TD Prediction
+// └──────────────────────────────────────────────────────────┘
+pub struct VNStepTD<S, A, P: StatePolicy<S, A>, E: Env<S, A>> {
+    pub value_function: ValueTable<S>,
+    pub gamma: f64,
+    n: usize,
+    buffer: VecDeque<(S, f64)>,
+    _action_type: std::marker::PhantomData<A>,
+    _policy_type: std::marker::PhantomData<P>,
+    _env_type: std::marker::PhantomData<E>,
+}
+
+impl<S: Hash + Eq + Copy, A: Clone, P: StatePolicy<S, A>, E: Env<S, A>> VNStepTD<S, A, P, E> {
+    pub fn new(n: usize, gamma: f64, c: f64, eta: f64) -> Self {
+        assert!(n > 0, "n must be positive");
+        Self {
+            value_function: ValueTable::new(c, eta),
+            gamma,
+            n,
+            buffer: VecDeque::with_capacity(n),
+            _action_type: std::marker::PhantomData,
+            _policy_type: std::marker::PhantomData,
+            _env_type: std::marker::PhantomData,
+        }
+    }
+
+    /// Discard the transitions of an unfinished episode
+    pub fn reset_buffer(&mut self) {
+        self.buffer.clear();
+    }
+
+    /// Update the oldest buffered state with the n-step return (or less at the end of an episode)
+    #[allow(non_snake_case)]
+    fn update_front(&mut self, bootstrap: f64) {
+        let G = self
+            .buffer
+            .iter()
+            .rev()
+            .fold(bootstrap, |acc, (_, r)| r + self.gamma * acc);
+
+        let (s, _) = self.buffer.pop_front().unwrap();
+        let v = self.value_function.value(&s);
+        let alpha = self.value_function.get_alpha();
+        self.value_function.update_value(s, v + alpha * (G - v));
+        self.value_function.increment_count();
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Clone, P: StatePolicy<S, A>, E: Env<S, A>> Agent<S, A, P, E>
+    for VNStepTD<S, A, P, E>
+{
+    // Information = Step (s, r, s', done)
+    type Information = (S, f64, Option<S>, bool);
+
+    fn get_action_value(&self, _state: &S, _action: &A) -> f64 {
+        unimplemented!()
+    }
+
+    fn get_value(&self, state: &S) -> f64 {
+        self.value_function.value(state)
+    }
+
+    fn select_action(&self, state: &S, policy: &mut P, env: &E) -> Option<A> {
+        let candidates = lookahead_candidates(state, env, |s| self.get_value(s));
+        policy.select_action_at(state, &candidates)
+    }
+
+    fn update(&mut self, info: &Self::Information) {
+        let (s, r, s_next, done) = info;
+        self.buffer.push_back((*s, *r));
+
+        let bootstrap = match s_next {
+            Some(s_next) => self.get_value(s_next),
+            None => 0f64,
+        };
+
+        if self.buffer.len() == self.n {
+            self.update_front(bootstrap);
+        }
+
+        // Flush the remaining (shorter) returns at the end of the episode
+        if s_next.is_none() || *done {
+            while !self.buffer.is_empty() {
+                self.update_front(bootstrap);
+            }
+        }
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Clone, P: StatePolicy<S, A>, E: Env<S, A>> Trainable<S, A, P, E>
+    for VNStepTD<S, A, P, E>
+{
+    fn learning(&self) -> Learning {
+        Learning::PerStep
+    }
+
+    fn information(&self, episode: &[Transition<S, A>], _policy: &P) -> Self::Information {
+        let t = episode.last().unwrap();
+        (t.state, t.reward, t.next_state, t.truncated)
+    }
+
+    fn begin_episode(&mut self) {
+        self.reset_buffer();
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  n-step SARSA
+// └──────────────────────────────────────────────────────────┘
+pub struct NStepSARSA<S, A, P: StatePolicy<S, A>, E: Env<S, A>> {
+    pub q_table: QTable<S, A>,
+    pub gamma: f64,
+    n: usize,
+    buffer: VecDeque<(S, A, f64)>,
+    _action_type: std::marker::PhantomData<A>,
+    _policy_type: std::marker::PhantomData<P>,
+    _env_type: std::marker::PhantomData<E>,
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> NStepSARSA<S, A, P, E> {
+    pub fn new(n: usize, gamma: f64, c: f64, eta: f64) -> Self {
+        assert!(n > 0, "n must be positive");
+        Self {
+            q_table: QTable::new(c, eta),
+            gamma,
+            n,
+            buffer: VecDeque::with_capacity(n),
+            _action_type: std::marker::PhantomData,
+            _policy_type: std::marker::PhantomData,
+            _env_type: std::marker::PhantomData,
+        }
+    }
+
+    /// Discard the transitions of an unfinished episode
+    pub fn reset_buffer(&mut self) {
+        self.buffer.clear();
+    }
+
+    /// Update the oldest buffered pair with the n-step return (or less at the end of an episode)
+    #[allow(non_snake_case)]
+    fn update_front(&mut self, bootstrap: f64) {
+        let G = self
+            .buffer
+            .iter()
+            .rev()
+            .fold(bootstrap, |acc, (_, _, r)| r + self.gamma * acc);
+
+        let (s, a, _) = self.buffer.pop_front().unwrap();
+        let q = self.q_table.value(&(s, a));
+        let alpha = self.q_table.get_alpha();
+        self.q_table.update_value((s, a), q + alpha * (G - q));
+        self.q_table.increment_count();
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Agent<S, A, P, E>
+    for NStepSARSA<S, A, P, E>
+{
+    // Information = Step (s, a, r, s', a', done)
+    type Information = (S, A, f64, Option<S>, Option<A>, bool);
+
+    fn get_action_value(&self, state: &S, action: &A) -> f64 {
+        self.q_table.value(&(*state, *action))
+    }
+
+    fn get_value(&self, state: &S) -> f64 {
+        self.q_table.max_value(state)
+    }
+
+    fn select_action(&self, state: &S, policy: &mut P, env: &E) -> Option<A> {
+        let actions = env.available_actions(state);
+        let candidates = actions
+            .iter()
+            .map(|a| (*a, self.get_action_value(state, a)))
+            .collect::<Vec<_>>();
+
+        policy.select_action_at(state, &candidates)
+    }
+
+    fn update(&mut self, info: &Self::Information) {
+        let (s, a, r, s_next, a_next, done) = info;
+        self.buffer.push_back((*s, *a, *r));
+
+        let bootstrap = match (s_next, a_next) {
+            (Some(s_next), Some(a_next)) => self.get_action_value(s_next, a_next),
+            _ => 0f64,
+        };
+
+        if self.buffer.len() == self.n {
+            self.update_front(bootstrap);
+        }
+
+        // Flush the remaining (shorter) returns at the end of the episode
+        if s_next.is_none() || *done {
+            while !self.buffer.is_empty() {
+                self.update_front(bootstrap);
+            }
+        }
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Trainable<S, A, P, E>
+    for NStepSARSA<S, A, P, E>
+{
+    fn learning(&self) -> Learning {
+        Learning::PerStep
+    }
+
+    fn information(&self, episode: &[Transition<S, A>], _policy: &P) -> Self::Information {
+        let t = episode.last().unwrap();
+        (t.state, t.action, t.reward, t.next_state, t.next_action, t.truncated)
+    }
+
+    fn begin_episode(&mut self) {
+        self.reset_buffer();
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  n-step Q-Learning
+//  - Bootstraps from max_a Q(s_{t+n}, a) without importance sampling correction
+// └──────────────────────────────────────────────────────────┘
+pub struct NStepQ<S, A, P: StatePolicy<S, A>, E: Env<S, A>> {
+    pub q_table: QTable<S, A>,
+    pub gamma: f64,
+    n: usize,
+    buffer: VecDeque<(S, A, f64)>,
+    _action_type: std::marker::PhantomData<A>,
+    _policy_type: std::marker::PhantomData<P>,
+    _env_type: std::marker::PhantomData<E>,
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> NStepQ<S, A, P, E> {
+    pub fn new(n: usize, gamma: f64, c: f64, eta: f64) -> Self {
+        assert!(n > 0, "n must be positive");
+        Self {
+            q_table: QTable::new(c, eta),
+            gamma,
+            n,
+            buffer: VecDeque::with_capacity(n),
+            _action_type: std::marker::PhantomData,
+            _policy_type: std::marker::PhantomData,
+            _env_type: std::marker::PhantomData,
+        }
+    }
+
+    /// Discard the transitions of an unfinished episode
+    pub fn reset_buffer(&mut self) {
+        self.buffer.clear();
+    }
+
+    /// Update the oldest buffered pair with the n-step return (or less at the end of an episode)
+    #[allow(non_snake_case)]
+    fn update_front(&mut self, bootstrap: f64) {
+        let G = self
+            .buffer
+            .iter()
+            .rev()
+            .fold(bootstrap, |acc, (_, _, r)| r + self.gamma * acc);
+
+        let (s, a, _) = self.buffer.pop_front().unwrap();
+        let q = self.q_table.value(&(s, a));
+        let alpha = self.q_table.get_alpha();
+        self.q_table.update_value((s, a), q + alpha * (G - q));
+        self.q_table.increment_count();
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Agent<S, A, P, E>
+    for NStepQ<S, A, P, E>
+{
+    // Information = Step (s, a, r, s', available actions in s', done)
+    type Information = (S, A, f64, Option<S>, Vec<A>, bool);
+
+    fn get_action_value(&self, state: &S, action: &A) -> f64 {
+        self.q_table.value(&(*state, *action))
+    }
+
+    fn get_value(&self, state: &S) -> f64 {
+        self.q_table.max_value(state)
+    }
+
+    fn select_action(&self, state: &S, policy: &mut P, env: &E) -> Option<A> {
+        let actions = env.available_actions(state);
+        let candidates = actions
+            .iter()
+            .map(|a| (*a, self.get_action_value(state, a)))
+            .collect::<Vec<_>>();
+
+        policy.select_action_at(state, &candidates)
+    }
+
+    fn update(&mut self, info: &Self::Information) {
+        let (s, a, r, s_next, a_next, done) = info;
+        self.buffer.push_back((*s, *a, *r));
+
+        let bootstrap = match s_next {
+            Some(s_next) if !a_next.is_empty() => a_next
+                .iter()
+                .map(|a_prime| self.get_action_value(s_next, a_prime))
+                .fold(f64::MIN, f64::max),
+            _ => 0f64,
+        };
+
+        if self.buffer.len() == self.n {
+            self.update_front(bootstrap);
+        }
+
+        // Flush the remaining (shorter) returns at the end of the episode
+        if s_next.is_none() || *done {
+            while !self.buffer.is_empty() {
+                self.update_front(bootstrap);
+            }
+        }
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Trainable<S, A, P, E>
+    for NStepQ<S, A, P, E>
+{
+    fn learning(&self) -> Learning {
+        Learning::PerStep
+    }
+
+    fn information(&self, episode: &[Transition<S, A>], _policy: &P) -> Self::Information {
+        let t = episode.last().unwrap();
+        (t.state, t.action, t.reward, t.next_state, t.next_actions.clone(), t.truncated)
+    }
+
+    fn begin_episode(&mut self) {
+        self.reset_buffer();
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Eligibility Traces
+// └──────────────────────────────────────────────────────────┘
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Trace {
+    /// e(s) ← e(s) + 1
+    Accumulating,
+    /// e(s) ← 1
+    Replacing,
+    /// e(s) ← (1 - α) e(s) + 1
+    Dutch,
+}
+
+impl Trace {
+    fn visit(&self, e: &mut f64, alpha: f64) {
+        match self {
+            Trace::Accumulating => *e += 1f64,
+            Trace::Replacing => *e = 1f64,
+            Trace::Dutch => *e = (1f64 - alpha) * *e + 1f64,
+        }
+    }
+}
+
+/// Traces below this magnitude are dropped
+const TRACE_EPS: f64 = 1e-10;
+
+// ┌──────────────────────────────────────────────────────────┐
+//  TD(λ) Prediction
+// └──────────────────────────────────────────────────────────┘
+pub struct TDLambda<S, A, P: StatePolicy<S, A>, E: Env<S, A>> {
+    pub value_function: ValueTable<S>,
+    pub traces: HashMap<S, f64>,
+    pub gamma: f64,
+    lambda: f64,
+    trace: Trace,
+    _action_type: std::marker::PhantomData<A>,
+    _policy_type: std::marker::PhantomData<P>,
+    _env_type: std::marker::PhantomData<E>,
+}
+
+impl<S: Hash + Eq + Copy, A: Clone, P: StatePolicy<S, A>, E: Env<S, A>> TDLambda<S, A, P, E> {
+    pub fn new(gamma: f64, lambda: f64, c: f64, eta: f64) -> Self {
+        Self {
+            value_function: ValueTable::new(c, eta),
+            traces: HashMap::new(),
+            gamma,
+            lambda,
+            trace: Trace::Accumulating,
+            _action_type: std::marker::PhantomData,
+            _policy_type: std::marker::PhantomData,
+            _env_type: std::marker::PhantomData,
+        }
+    }
+
+    pub fn with_trace(mut self, trace: Trace) -> Self {
+        self.trace = trace;
+        self
+    }
+
+    pub fn reset_traces(&mut self) {
+        self.traces.clear();
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Clone, P: StatePolicy<S, A>, E: Env<S, A>> Agent<S, A, P, E>
+    for TDLambda<S, A, P, E>
+{
+    // Information = Step (s, r, s')
+    type Information = (S, f64, Option<S>);
+
+    fn get_action_value(&self, _state: &S, _action: &A) -> f64 {
+        unimplemented!()
+    }
+
+    fn get_value(&self, state: &S) -> f64 {
+        self.value_function.value(state)
+    }
+
+    fn select_action(&self, state: &S, policy: &mut P, env: &E) -> Option<A> {
+        let candidates = lookahead_candidates(state, env, |s| self.get_value(s));
+        policy.select_action_at(state, &candidates)
+    }
+
+    fn update(&mut self, info: &Self::Information) {
+        let (s, r, s_next) = info;
+
+        let v_next = s_next.map_or(0f64, |s_next| self.get_value(&s_next));
+        let delta = r + self.gamma * v_next - self.get_value(s);
+
+        let alpha = self.value_function.get_alpha();
+        self.trace.visit(self.traces.entry(*s).or_insert(0f64), alpha);
+
+        let decay = self.gamma * self.lambda;
+        for (state, e) in self.traces.iter_mut() {
+            self.value_function.add_value(*state, alpha * delta * *e);
+            *e *= decay;
+        }
+        self.traces.retain(|_, e| e.abs() > TRACE_EPS);
+
+        if s_next.is_none() {
+            self.reset_traces();
+        }
+        self.value_function.increment_count();
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Clone, P: StatePolicy<S, A>, E: Env<S, A>> Trainable<S, A, P, E>
+    for TDLambda<S, A, P, E>
+{
+    fn learning(&self) -> Learning {
+        Learning::PerStep
+    }
+
+    fn information(&self, episode: &[Transition<S, A>], _policy: &P) -> Self::Information {
+        let t = episode.last().unwrap();
+        (t.state, t.reward, t.next_state)
+    }
+
+    fn begin_episode(&mut self) {
+        self.reset_traces();
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  SARSA(λ)
+// └──────────────────────────────────────────────────────────┘
+pub struct SARSALambda<S, A, P: StatePolicy<S, A>, E: Env<S, A>> {
+    pub q_table: QTable<S, A>,
+    pub traces: HashMap<(S, A), f64>,
+    pub gamma: f64,
+    lambda: f64,
+    trace: Trace,
+    _action_type: std::marker::PhantomData<A>,
+    _policy_type: std::marker::PhantomData<P>,
+    _env_type: std::marker::PhantomData<E>,
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> SARSALambda<S, A, P, E> {
+    pub fn new(gamma: f64, lambda: f64, c: f64, eta: f64) -> Self {
+        Self {
+            q_table: QTable::new(c, eta),
+            traces: HashMap::new(),
+            gamma,
+            lambda,
+            trace: Trace::Accumulating,
+            _action_type: std::marker::PhantomData,
+            _policy_type: std::marker::PhantomData,
+            _env_type: std::marker::PhantomData,
+        }
+    }
+
+    pub fn with_trace(mut self, trace: Trace) -> Self {
+        self.trace = trace;
+        self
+    }
+
+    pub fn reset_traces(&mut self) {
+        self.traces.clear();
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Agent<S, A, P, E>
+    for SARSALambda<S, A, P, E>
+{
+    // Information = Step (s, a, r, s', a')
+    type Information = (S, A, f64, Option<S>, Option<A>);
+
+    fn get_action_value(&self, state: &S, action: &A) -> f64 {
+        self.q_table.value(&(*state, *action))
+    }
+
+    fn get_value(&self, state: &S) -> f64 {
+        self.q_table.max_value(state)
+    }
+
+    fn select_action(&self, state: &S, policy: &mut P, env: &E) -> Option<A> {
+        let actions = env.available_actions(state);
+        let candidates = actions
+            .iter()
+            .map(|a| (*a, self.get_action_value(state, a)))
+            .collect::<Vec<_>>();
+
+        policy.select_action_at(state, &candidates)
+    }
+
+    fn update(&mut self, info: &Self::Information) {
+        let (s, a, r, s_next, a_next) = info;
+
+        let q_next = match (s_next, a_next) {
+            (Some(s_next), Some(a_next)) => self.get_action_value(s_next, a_next),
+            _ => 0f64,
+        };
+        let delta = r + self.gamma * q_next - self.get_action_value(s, a);
+
+        let alpha = self.q_table.get_alpha();
+        self.trace.visit(self.traces.entry((*s, *a)).or_insert(0f64), alpha);
+
+        let decay = self.gamma * self.lambda;
+        for (key, e) in self.traces.iter_mut() {
+            self.q_table.add_value(*key, alpha * delta * *e);
+            *e *= decay;
+        }
+        self.traces.retain(|_, e| e.abs() > TRACE_EPS);
+
+        if s_next.is_none() {
+            self.reset_traces();
+        }
+        self.q_table.increment_count();
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Trainable<S, A, P, E>
+    for SARSALambda<S, A, P, E>
+{
+    fn learning(&self) -> Learning {
+        Learning::PerStep
+    }
+
+    fn information(&self, episode: &[Transition<S, A>], _policy: &P) -> Self::Information {
+        let t = episode.last().unwrap();
+        (t.state, t.action, t.reward, t.next_state, t.next_action)
+    }
+
+    fn begin_episode(&mut self) {
+        self.reset_traces();
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Watkins Q(λ)
+//  - Traces are cut whenever the next action is exploratory (not greedy)
+// └──────────────────────────────────────────────────────────┘
+pub struct WatkinsQLambda<S, A, P: StatePolicy<S, A>, E: Env<S, A>> {
+    pub q_table: QTable<S, A>,
+    pub traces: HashMap<(S, A), f64>,
+    pub gamma: f64,
+    lambda: f64,
+    trace: Trace,
+    _action_type: std::marker::PhantomData<A>,
+    _policy_type: std::marker::PhantomData<P>,
+    _env_type: std::marker::PhantomData<E>,
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> WatkinsQLambda<S, A, P, E> {
+    pub fn new(gamma: f64, lambda: f64, c: f64, eta: f64) -> Self {
+        Self {
+            q_table: QTable::new(c, eta),
+            traces: HashMap::new(),
+            gamma,
+            lambda,
+            trace: Trace::Accumulating,
+            _action_type: std::marker::PhantomData,
+            _policy_type: std::marker::PhantomData,
+            _env_type: std::marker::PhantomData,
+        }
+    }
+
+    pub fn with_trace(mut self, trace: Trace) -> Self {
+        self.trace = trace;
+        self
+    }
+
+    pub fn reset_traces(&mut self) {
+        self.traces.clear();
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Agent<S, A, P, E>
+    for WatkinsQLambda<S, A, P, E>
+{
+    // Information = Step (s, a, r, s', a', available actions in s')
+    type Information = (S, A, f64, Option<S>, Option<A>, Vec<A>);
+
+    fn get_action_value(&self, state: &S, action: &A) -> f64 {
+        self.q_table.value(&(*state, *action))
+    }
+
+    fn get_value(&self, state: &S) -> f64 {
+        self.q_table.max_value(state)
+    }
+
+    fn select_action(&self, state: &S, policy: &mut P, env: &E) -> Option<A> {
+        let actions = env.available_actions(state);
+        let candidates = actions
+            .iter()
+            .map(|a| (*a, self.get_action_value(state, a)))
+            .collect::<Vec<_>>();
+
+        policy.select_action_at(state, &candidates)
+    }
+
+    fn update(&mut self, info: &Self::Information) {
+        let (s, a, r, s_next, a_next, a_pool) = info;
+
+        // Greedy value of the next state, and whether the next action attains it
+        let (q_next_max, greedy) = match s_next {
+            Some(s_next) if !a_pool.is_empty() => {
+                let q_max = a_pool
+                    .iter()
+                    .map(|a_prime| self.get_action_value(s_next, a_prime))
+                    .fold(f64::MIN, f64::max);
+                let greedy = a_next.is_some_and(|a_next| self.get_action_value(s_next, &a_next) == q_max);
+                (q_max, greedy)
+            }
+            _ => (0f64, false),
+        };
+        let delta = r + self.gamma * q_next_max - self.get_action_value(s, a);
+
+        let alpha = self.q_table.get_alpha();
+        self.trace.visit(self.traces.entry((*s, *a)).or_insert(0f64), alpha);
+
+        let decay = self.gamma * self.lambda;
+        for (key, e) in self.traces.iter_mut() {
+            self.q_table.add_value(*key, alpha * delta * *e);
+            *e *= decay;
+        }
+        self.traces.retain(|_, e| e.abs() > TRACE_EPS);
+
+        if !greedy {
+            self.reset_traces();
+        }
+        self.q_table.increment_count();
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Trainable<S, A, P, E>
+    for WatkinsQLambda<S, A, P, E>
+{
+    fn learning(&self) -> Learning {
+        Learning::PerStep
+    }
+
+    fn information(&self, episode: &[Transition<S, A>], _policy: &P) -> Self::Information {
+        let t = episode.last().unwrap();
+        (t.state, t.action, t.reward, t.next_state, t.next_action, t.next_actions.clone())
+    }
+
+    fn begin_episode(&mut self) {
+        self.reset_traces();
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Dyna-Q
+//  - Q-learning on real steps, plus `n_planning` updates on transitions replayed from a learned model
+//  - The model keeps the last observed (r, s', actions in s') of every (s, a) (deterministic model)
+//  - Dyna-Q+: planning rewards get a bonus κ √τ, τ the number of real steps since (s, a) was tried
+// └──────────────────────────────────────────────────────────┘
+type Model<S, A> = HashMap<(S, A), (f64, Option<S>, Vec<A>)>;
+
+pub struct DynaQ<S, A, P: StatePolicy<S, A>, E: Env<S, A>> {
+    pub q_table: QTable<S, A>,
+    pub model: Model<S, A>,
+    pub gamma: f64,
+    n_planning: usize,
+    kappa: f64,
+    observed: Vec<(S, A)>,
+    last_visit: HashMap<(S, A), usize>,
+    steps: usize,
+    rng: StdRng,
+    _action_type: std::marker::PhantomData<A>,
+    _policy_type: std::marker::PhantomData<P>,
+    _env_type: std::marker::PhantomData<E>,
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> DynaQ<S, A, P, E> {
+    pub fn new(gamma: f64, c: f64, eta: f64, n_planning: usize) -> Self {
+        Self {
+            q_table: QTable::new(c, eta),
+            model: HashMap::new(),
+            gamma,
+            n_planning,
+            kappa: 0f64,
+            observed: vec![],
+            last_visit: HashMap::new(),
+            steps: 0,
+            rng: StdRng::from_entropy(),
+            _action_type: std::marker::PhantomData,
+            _policy_type: std::marker::PhantomData,
+            _env_type: std::marker::PhantomData,
+        }
+    }
+
+    /// Dyna-Q+ exploration bonus κ √τ for non-stationary environments
+    pub fn with_bonus(mut self, kappa: f64) -> Self {
+        self.kappa = kappa;
+        self
+    }
+
+    /// Seed the RNG used to sample planning transitions
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    pub fn with_rng(mut self, rng: StdRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    /// Forget the learned model (the action values are kept)
+    pub fn reset_model(&mut self) {
+        self.model.clear();
+        self.observed.clear();
+        self.last_visit.clear();
+        self.steps = 0;
+    }
+
+    /// Record a real transition in the model
+    pub fn observe(&mut self, state: &S, action: &A, reward: f64, next_state: Option<S>, next_actions: Vec<A>) {
+        let key = (*state, *action);
+        if self.model.insert(key, (reward, next_state, next_actions)).is_none() {
+            self.observed.push(key);
+        }
+        self.steps += 1;
+        self.last_visit.insert(key, self.steps);
+    }
+
+    /// `n_planning` Q-learning updates on transitions sampled uniformly from the model
+    pub fn plan(&mut self) {
+        if self.observed.is_empty() {
+            return;
+        }
+
+        for _ in 0..self.n_planning {
+            let (s, a) = *self.observed.choose(&mut self.rng).unwrap();
+            let (r, s_next, a_pool) = self.model[&(s, a)].clone();
+            let tau = (self.steps - self.last_visit[&(s, a)]) as f64;
+            let r = r + self.kappa * tau.sqrt();
+            self.q_learning_update(&s, &a, r, &s_next, &a_pool);
+        }
+    }
+
+    #[allow(non_snake_case)]
+    fn q_learning_update(&mut self, s: &S, a: &A, r: f64, s_next: &Option<S>, a_pool: &[A]) {
+        let target = match s_next {
+            Some(s_next) if !a_pool.is_empty() => {
+                let Q_next_max = a_pool
+                    .iter()
+                    .map(|a_prime| self.q_table.value(&(*s_next, *a_prime)))
+                    .fold(f64::NEG_INFINITY, f64::max);
+                r + self.gamma * Q_next_max
+            }
+            _ => r,
+        };
+
+        let alpha = self.q_table.get_alpha();
+        let delta = target - self.q_table.value(&(*s, *a));
+        self.q_table.add_value((*s, *a), delta * alpha);
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Agent<S, A, P, E>
+    for DynaQ<S, A, P, E>
+{
+    // Information = Step
+    type Information = (S, A, f64, Option<S>, Vec<A>);
+
+    fn get_action_value(&self, state: &S, action: &A) -> f64 {
+        self.q_table.value(&(*state, *action))
+    }
+
+    fn get_value(&self, state: &S) -> f64 {
+        self.q_table.max_value(state)
+    }
+
+    fn select_action(&self, state: &S, policy: &mut P, env: &E) -> Option<A> {
+        let actions = env.available_actions(state);
+        let candidates = actions
+            .iter()
+            .map(|a| (*a, self.get_action_value(state, a)))
+            .collect::<Vec<_>>();
+
+        policy.select_action_at(state, &candidates)
+    }
+
+    fn update(&mut self, info: &Self::Information) {
+        let (s, a, r, s_next, a_pool) = info;
+
+        // Direct RL, model learning, then planning
+        self.q_learning_update(s, a, *r, s_next, a_pool);
+        self.observe(s, a, *r, *s_next, a_pool.clone());
+        self.plan();
+        self.q_table.increment_count();
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Trainable<S, A, P, E>
+    for DynaQ<S, A, P, E>
+{
+    fn learning(&self) -> Learning {
+        Learning::PerStep
+    }
+
+    fn information(&self, episode: &[Transition<S, A>], _policy: &P) -> Self::Information {
+        let t = episode.last().unwrap();
+        (t.state, t.action, t.reward, t.next_state, t.next_actions.clone())
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Prioritized Sweeping
+//  - Learns the same deterministic model as Dyna-Q, plus the predecessors of every state
+//  - Planning updates the (s, a) with the largest |TD error| first, then queues its predecessors
+//  - Pairs whose priority does not exceed `threshold` are not queued
+//  - Q is only updated by planning (the real step just queues its pair), so `n_planning` must be positive
+// └──────────────────────────────────────────────────────────┘
+/// Max-heap entry ordered by priority
+struct Prioritized<K> {
+    priority: f64,
+    key: K,
+}
+
+impl<K> PartialEq for Prioritized<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority.total_cmp(&other.priority) == Ordering::Equal
+    }
+}
+
+impl<K> Eq for Prioritized<K> {}
+
+impl<K> PartialOrd for Prioritized<K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K> Ord for Prioritized<K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.total_cmp(&other.priority)
+    }
+}
+
+pub struct PrioritizedSweeping<S, A, P: StatePolicy<S, A>, E: Env<S, A>> {
+    pub q_table: QTable<S, A>,
+    pub model: Model<S, A>,
+    pub predecessors: HashMap<S, Vec<(S, A)>>,
+    pub gamma: f64,
+    n_planning: usize,
+    threshold: f64,
+    queue: BinaryHeap<Prioritized<(S, A)>>,
+    priorities: HashMap<(S, A), f64>,
+    _action_type: std::marker::PhantomData<A>,
+    _policy_type: std::marker::PhantomData<P>,
+    _env_type: std::marker::PhantomData<E>,
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> PrioritizedSweeping<S, A, P, E> {
+    pub fn new(gamma: f64, c: f64, eta: f64, n_planning: usize) -> Self {
+        assert!(n_planning > 0, "prioritized sweeping learns only by planning: n_planning must be positive");
+        Self {
+            q_table: QTable::new(c, eta),
+            model: HashMap::new(),
+            predecessors: HashMap::new(),
+            gamma,
+            n_planning,
+            threshold: 1e-4,
+            queue: BinaryHeap::new(),
+            priorities: HashMap::new(),
+            _action_type: std::marker::PhantomData,
+            _policy_type: std::marker::PhantomData,
+            _env_type: std::marker::PhantomData,
+        }
+    }
+
+    /// Minimum |TD error| for a state-action pair to be queued
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Number of state-action pairs waiting in the priority queue
+    pub fn queue_len(&self) -> usize {
+        self.priorities.len()
+    }
+
+    /// Record a real transition in the model and the predecessor index
+    pub fn observe(&mut self, state: &S, action: &A, reward: f64, next_state: Option<S>, next_actions: Vec<A>) {
+        if let Some(s_next) = next_state {
+            let preds = self.predecessors.entry(s_next).or_default();
+            if !preds.contains(&(*state, *action)) {
+                preds.push((*state, *action));
+            }
+        }
+        self.model.insert((*state, *action), (reward, next_state, next_actions));
+    }
+
+    /// Up to `n_planning` updates in order of priority, propagating backward through predecessors
+    pub fn plan(&mut self) {
+        for _ in 0..self.n_planning {
+            let Some((s, a)) = self.pop() else {
+                break;
+            };
+
+            let (r, s_next, a_pool) = self.model[&(s, a)].clone();
+            let delta = self.td_error(&s, &a, r, &s_next, &a_pool);
+            let alpha = self.q_table.get_alpha();
+            self.q_table.add_value((s, a), alpha * delta);
+
+            let preds = self.predecessors.get(&s).cloned().unwrap_or_default();
+            for (s_pred, a_pred) in preds {
+                let (r_pred, s_pred_next, a_pred_pool) = self.model[&(s_pred, a_pred)].clone();
+                let priority = self.td_error(&s_pred, &a_pred, r_pred, &s_pred_next, &a_pred_pool).abs();
+                self.push((s_pred, a_pred), priority);
+            }
+        }
+    }
+
+    #[allow(non_snake_case)]
+    fn td_error(&self, s: &S, a: &A, r: f64, s_next: &Option<S>, a_pool: &[A]) -> f64 {
+        let target = match s_next {
+            Some(s_next) if !a_pool.is_empty() => {
+                let Q_next_max = a_pool
+                    .iter()
+                    .map(|a_prime| self.q_table.value(&(*s_next, *a_prime)))
+                    .fold(f64::NEG_INFINITY, f64::max);
+                r + self.gamma * Q_next_max
+            }
+            _ => r,
+        };
+        target - self.q_table.value(&(*s, *a))
+    }
+
+    /// Queue `key` unless it is below the threshold or already queued with a higher priority
+    fn push(&mut self, key: (S, A), priority: f64) {
+        if priority <= self.threshold {
+            return;
+        }
+        if self.priorities.get(&key).is_some_and(|p| *p >= priority) {
+            return;
+        }
+        self.priorities.insert(key, priority);
+        self.queue.push(Prioritized { priority, key });
+    }
+
+    /// Pop the pair with the highest priority, skipping entries superseded by a later `push`
+    fn pop(&mut self) -> Option<(S, A)> {
+        while let Some(Prioritized { priority, key }) = self.queue.pop() {
+            if self.priorities.get(&key) == Some(&priority) {
+                self.priorities.remove(&key);
+                return Some(key);
+            }
+        }
+        None
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Agent<S, A, P, E>
+    for PrioritizedSweeping<S, A, P, E>
+{
+    // Information = Step
+    type Information = (S, A, f64, Option<S>, Vec<A>);
+
+    fn get_action_value(&self, state: &S, action: &A) -> f64 {
+        self.q_table.value(&(*state, *action))
+    }
+
+    fn get_value(&self, state: &S) -> f64 {
+        self.q_table.max_value(state)
+    }
+
+    fn select_action(&self, state: &S, policy: &mut P, env: &E) -> Option<A> {
+        let actions = env.available_actions(state);
+        let candidates = actions
+            .iter()
+            .map(|a| (*a, self.get_action_value(state, a)))
+            .collect::<Vec<_>>();
+
+        policy.select_action_at(state, &candidates)
+    }
+
+    fn update(&mut self, info: &Self::Information) {
+        let (s, a, r, s_next, a_pool) = info;
+
+        self.observe(s, a, *r, *s_next, a_pool.clone());
+        let priority = self.td_error(s, a, *r, s_next, a_pool).abs();
+        self.push((*s, *a), priority);
+        self.plan();
+        self.q_table.increment_count();
+    }
+}
+
+impl<S: Hash + Eq + Copy, A: Hash + Eq + Copy, P: StatePolicy<S, A>, E: Env<S, A>> Trainable<S, A, P, E>
+    for PrioritizedSweeping<S, A, P, E>
+{
+    fn learning(&self) -> Learning {
+        Learning::PerStep
+    }
+
+    fn information(&self, episode: &[Transition<S, A>], _policy: &P) -> Self::Information {
+        let t = episode.last().unwrap();
+        (t.state, t.action, t.reward, t.next_state, t.next_actions.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::env::episodic::Episodic;
+    use crate::env::tabular::TabularMdp;
+    use crate::policy::EGreedyPolicy;
+    use crate::trainer::Trainer;
+
+    type M = TabularMdp<usize, &'static str>;
+    type P = EGreedyPolicy<&'static str>;
+
+    // Q ← Q + α (r + γ Q(s', a*) − Q): with Q(0, go) = 3, Q(1, ·) = {2, 5}, r = 1, γ = 0.9, α = 0.5
+    #[test]
+    fn qtd0_single_update() {
+        let mut agent = QTD0::<usize, &str, P, M>::new(0.9, 0.5, 0.0);
+        agent.update_value(&0, &"go", 3.0);
+        agent.update_value(&1, &"a", 2.0);
+        agent.update_value(&1, &"b", 5.0);
+
+        agent.update(&(0, "go", 1.0, Some(1), vec!["a", "b"]));
+        assert!((agent.get_action_value(&0, &"go") - (3.0 + 0.5 * (1.0 + 0.9 * 5.0 - 3.0))).abs() < 1e-12);
+
+        agent.update(&(1, "b", 1.0, None, vec![]));
+        assert!((agent.get_action_value(&1, &"b") - (5.0 + 0.5 * (1.0 - 5.0))).abs() < 1e-12);
+    }
+
+    #[test]
+    fn qtd0_min_single_update() {
+        let mut agent = QTD0Min::<usize, &str, P, M>::new(0.9, 0.5, 0.0);
+        agent.update_value(&0, &"go", 3.0);
+        agent.update_value(&1, &"a", 2.0);
+        agent.update_value(&1, &"b", 5.0);
+
+        agent.update(&(0, "go", 1.0, Some(1), vec!["a", "b"]));
+        assert!((agent.get_action_value(&0, &"go") - (3.0 + 0.5 * (1.0 + 0.9 * 2.0 - 3.0))).abs() < 1e-12);
+    }
+
+    // `with_transition` adds the successor as a state without actions
+    #[test]
+    fn qtd0_learns_into_state_without_actions() {
+        let env = TabularMdp::new().with_transition(0, "go", Some(1), 1.0, 1.0);
+        let mut env = Episodic::new(env, 0).with_max_steps(10);
+        let mut policy = P::new(0.1, 1.0).with_seed(0);
+        let mut agent = QTD0::<usize, &str, P, M>::new(0.9, 0.5, 0.0).with_seed(0);
+        let mut agent_min = QTD0Min::<usize, &str, P, M>::new(0.9, 0.5, 0.0).with_seed(0);
+
+        Trainer::new(2).train(&mut agent, &mut policy, &mut env);
+        Trainer::new(2).train(&mut agent_min, &mut policy, &mut env);
+
+        assert!((agent.get_action_value(&0, &"go") - 0.75).abs() < 1e-12);
+        assert!((agent_min.get_action_value(&0, &"go") - 0.75).abs() < 1e-12);
+    }
+
+    // Chain 0 → 1 → 2 → 3 with rewards 1, 2, 4; n = 2, γ = 0.5, α = 1 and a value of 8 in state 3.
+    // Terminated after state 2: G(0) = 1 + γ 2 + γ² V(2) = 2, G(1) = 2 + γ 4 = 4, G(2) = 4.
+    // Truncated in state 3:     G(0) = 2, G(1) = 2 + γ 4 + γ² 8 = 6, G(2) = 4 + γ 8 = 8.
+    const REWARDS: [f64; 3] = [1.0, 2.0, 4.0];
+
+    #[test]
+    fn vnstep_td_returns() {
+        for (truncated, expected) in [(false, [2.0, 4.0, 4.0]), (true, [2.0, 6.0, 8.0])] {
+            let mut agent = VNStepTD::<usize, &str, P, M>::new(2, 0.5, 1.0, 0.0);
+            agent.value_function.update_value(3, 8.0);
+            for (s, r) in REWARDS.iter().enumerate() {
+                let s_next = if s < 2 || truncated { Some(s + 1) } else { None };
+                agent.update(&(s, *r, s_next, s == 2 && truncated));
+            }
+            for (s, v) in expected.iter().enumerate() {
+                assert!((agent.get_value(&s) - v).abs() < 1e-12, "V({}) with truncated = {}", s, truncated);
+            }
+        }
+    }
+
+    #[test]
+    fn nstep_sarsa_returns() {
+        for (truncated, expected) in [(false, [2.0, 4.0, 4.0]), (true, [2.0, 6.0, 8.0])] {
+            let mut agent = NStepSARSA::<usize, &str, P, M>::new(2, 0.5, 1.0, 0.0);
+            // Bootstraps from the selected next action, not the greedy one
+            agent.q_table.update_value((3, "a"), 16.0);
+            agent.q_table.update_value((3, "b"), 8.0);
+            for (s, r) in REWARDS.iter().enumerate() {
+                let a_next = if s < 2 { "a" } else { "b" };
+                let (s_next, a_next) = if s < 2 || truncated { (Some(s + 1), Some(a_next)) } else { (None, None) };
+                agent.update(&(s, "a", *r, s_next, a_next, s == 2 && truncated));
+            }
+            for (s, q) in expected.iter().enumerate() {
+                assert!((agent.get_action_value(&s, &"a") - q).abs() < 1e-12, "Q({}, a) with truncated = {}", s, truncated);
+            }
+        }
+    }
+
+    #[test]
+    fn nstep_q_returns() {
+        for (truncated, expected) in [(false, [2.0, 4.0, 4.0]), (true, [2.0, 6.0, 8.0])] {
+            let mut agent = NStepQ::<usize, &str, P, M>::new(2, 0.5, 1.0, 0.0);
+            agent.q_table.update_value((3, "a"), -1.0);
+            agent.q_table.update_value((3, "b"), 8.0);
+            for (s, r) in REWARDS.iter().enumerate() {
+                let s_next = if s < 2 || truncated { Some(s + 1) } else { None };
+                let a_next = if s_next.is_some() { vec!["a", "b"] } else { vec![] };
+                agent.update(&(s, "a", *r, s_next, a_next, s == 2 && truncated));
+            }
+            for (s, q) in expected.iter().enumerate() {
+                assert!((agent.get_action_value(&s, &"a") - q).abs() < 1e-12, "Q({}, a) with truncated = {}", s, truncated);
+            }
+        }
+    }
+
+    #[test]
+    fn prioritized_sweeping_queue() {
+        let mut agent = PrioritizedSweeping::<usize, &str, P, M>::new(0.9, 0.5, 0.0, 1).with_threshold(0.1);
+        agent.push((0, "a"), 0.2);
+        agent.push((1, "a"), 0.5);
+        // A higher priority supersedes the queued entry, a lower one is ignored
+        agent.push((0, "a"), 0.7);
+        agent.push((0, "a"), 0.3);
+        // Not above the threshold
+        agent.push((2, "a"), 0.05);
+        agent.push((2, "b"), 0.1);
+        assert_eq!(agent.queue_len(), 2);
+
+        assert_eq!(agent.pop(), Some((0, "a")));
+        assert_eq!(agent.queue_len(), 1);
+        assert_eq!(agent.pop(), Some((1, "a")));
+        // The superseded entry for (0, a) is skipped
+        assert_eq!(agent.pop(), None);
+        assert_eq!(agent.queue_len(), 0);
     }
 }
@@ -0,0 +1,464 @@
+use peroxide::fuga::*;
+
+use crate::agent::StepSize;
+use crate::bandit::{cholesky, sample_beta, sample_normal, BanditAgent, ContextualBanditAgent};
+use crate::policy::StatePolicy;
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Action-value Bandit
+//  - Incremental estimates Q(a) += α(N(a)) (R - Q(a))
+//  - Exploration is delegated to a `Policy` over arms:
+//    `EGreedyPolicy` (ε-greedy), `SoftmaxPolicy`, `UCBPolicy` (UCB1)
+// └──────────────────────────────────────────────────────────┘
+pub struct ActionValueBandit<P: StatePolicy<(), usize>> {
+    pub q: Vec<f64>,
+    pub n: Vec<usize>,
+    step_size: StepSize,
+    policy: P,
+}
+
+impl<P: StatePolicy<(), usize>> ActionValueBandit<P> {
+    /// Sample-average estimates starting from zero
+    pub fn new(k: usize, policy: P) -> Self {
+        Self {
+            q: vec![0f64; k],
+            n: vec![0; k],
+            step_size: StepSize::SampleAverage,
+            policy,
+        }
+    }
+
+    /// e.g. `StepSize::Constant(0.1)` to track non-stationary arms
+    pub fn with_step_size(mut self, step_size: StepSize) -> Self {
+        self.step_size = step_size;
+        self
+    }
+
+    /// Optimistic initial values
+    pub fn with_initial_value(mut self, q0: f64) -> Self {
+        self.q = vec![q0; self.q.len()];
+        self
+    }
+
+    pub fn policy(&self) -> &P {
+        &self.policy
+    }
+
+    pub fn policy_mut(&mut self) -> &mut P {
+        &mut self.policy
+    }
+}
+
+impl<P: StatePolicy<(), usize>> BanditAgent for ActionValueBandit<P> {
+    fn select_arm(&mut self) -> usize {
+        let candidates = self.q.iter().copied().enumerate().collect::<Vec<_>>();
+        self.policy.select_action(&candidates).unwrap()
+    }
+
+    fn update(&mut self, arm: usize, reward: f64) {
+        self.policy.observe_action_at(&(), &arm);
+        self.n[arm] += 1;
+        let alpha = self.step_size.alpha(self.n[arm]);
+        self.q[arm] += alpha * (reward - self.q[arm]);
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Gradient Bandit
+//  - Preferences H(a) with π = softmax(H)
+//  - H(a) += α (R - R̄) (1{a = A} - π(a)), R̄ the average reward (baseline)
+// └──────────────────────────────────────────────────────────┘
+pub struct GradientBandit {
+    pub h: Vec<f64>,
+    alpha: f64,
+    baseline: bool,
+    mean_reward: f64,
+    t: usize,
+    rng: StdRng,
+}
+
+impl GradientBandit {
+    pub fn new(k: usize, alpha: f64) -> Self {
+        Self {
+            h: vec![0f64; k],
+            alpha,
+            baseline: true,
+            mean_reward: 0f64,
+            t: 0,
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// Use the average reward as baseline (default) or zero
+    pub fn with_baseline(mut self, baseline: bool) -> Self {
+        self.baseline = baseline;
+        self
+    }
+
+    /// Seed the RNG used for sampling arms
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    pub fn with_rng(mut self, rng: StdRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    /// π(a) = exp(H(a)) / Σ exp(H(b))
+    pub fn probabilities(&self) -> Vec<f64> {
+        let h_max = self.h.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let weights = self.h.iter().map(|h| (h - h_max).exp()).collect::<Vec<_>>();
+        let total = weights.iter().sum::<f64>();
+        weights.into_iter().map(|w| w / total).collect()
+    }
+}
+
+impl BanditAgent for GradientBandit {
+    fn select_arm(&mut self) -> usize {
+        let probs = self.probabilities();
+        let sample: f64 = self.rng.gen();
+
+        let mut cumulative = 0f64;
+        for (arm, p) in probs.iter().enumerate() {
+            cumulative += p;
+            if sample < cumulative {
+                return arm;
+            }
+        }
+        probs.len() - 1
+    }
+
+    fn update(&mut self, arm: usize, reward: f64) {
+        self.t += 1;
+        if self.baseline {
+            self.mean_reward += (reward - self.mean_reward) / self.t as f64;
+        }
+
+        let probs = self.probabilities();
+        let advantage = reward - self.mean_reward;
+        for (a, (h, p)) in self.h.iter_mut().zip(probs).enumerate() {
+            let indicator = if a == arm { 1f64 } else { 0f64 };
+            *h += self.alpha * advantage * (indicator - p);
+        }
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Thompson Sampling - Bernoulli arms
+//  - Beta(α_a, β_a) posterior of each arm's success probability
+//  - Rewards in [0, 1] are counted as fractional successes
+// └──────────────────────────────────────────────────────────┘
+pub struct BernoulliThompson {
+    pub alpha: Vec<f64>,
+    pub beta: Vec<f64>,
+    rng: StdRng,
+}
+
+impl BernoulliThompson {
+    /// Uniform Beta(1, 1) prior
+    pub fn new(k: usize) -> Self {
+        Self {
+            alpha: vec![1f64; k],
+            beta: vec![1f64; k],
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    pub fn with_prior(mut self, alpha: f64, beta: f64) -> Self {
+        assert!(alpha > 0f64 && beta > 0f64, "prior parameters must be positive");
+        self.alpha = vec![alpha; self.alpha.len()];
+        self.beta = vec![beta; self.beta.len()];
+        self
+    }
+
+    /// Seed the RNG used for posterior sampling
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    pub fn with_rng(mut self, rng: StdRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    /// Posterior means α / (α + β)
+    pub fn posterior_mean(&self) -> Vec<f64> {
+        self.alpha.iter().zip(self.beta.iter()).map(|(a, b)| a / (a + b)).collect()
+    }
+}
+
+impl BanditAgent for BernoulliThompson {
+    fn select_arm(&mut self) -> usize {
+        let samples = self
+            .alpha
+            .iter()
+            .zip(self.beta.iter())
+            .map(|(a, b)| sample_beta(&mut self.rng, *a, *b))
+            .collect::<Vec<_>>();
+        (0..samples.len()).fold(0, |best, i| if samples[i] > samples[best] { i } else { best })
+    }
+
+    fn update(&mut self, arm: usize, reward: f64) {
+        assert!((0f64..=1f64).contains(&reward), "rewards must lie in [0, 1]");
+        self.alpha[arm] += reward;
+        self.beta[arm] += 1f64 - reward;
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Thompson Sampling - Gaussian arms
+//  - N(m_a, 1 / τ_a) posterior of each arm's mean, for rewards with known noise σ
+// └──────────────────────────────────────────────────────────┘
+pub struct GaussianThompson {
+    pub mean: Vec<f64>,
+    pub precision: Vec<f64>,
+    noise_std: f64,
+    rng: StdRng,
+}
+
+impl GaussianThompson {
+    /// N(0, 1) prior and unit reward noise, as in the k-armed testbed
+    pub fn new(k: usize) -> Self {
+        Self {
+            mean: vec![0f64; k],
+            precision: vec![1f64; k],
+            noise_std: 1f64,
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    pub fn with_prior(mut self, mean: f64, std: f64) -> Self {
+        assert!(std > 0f64, "prior standard deviation must be positive");
+        self.mean = vec![mean; self.mean.len()];
+        self.precision = vec![1f64 / (std * std); self.precision.len()];
+        self
+    }
+
+    pub fn with_noise_std(mut self, noise_std: f64) -> Self {
+        assert!(noise_std > 0f64, "noise standard deviation must be positive");
+        self.noise_std = noise_std;
+        self
+    }
+
+    /// Seed the RNG used for posterior sampling
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    pub fn with_rng(mut self, rng: StdRng) -> Self {
+        self.rng = rng;
+        self
+    }
+}
+
+impl BanditAgent for GaussianThompson {
+    fn select_arm(&mut self) -> usize {
+        let samples = self
+            .mean
+            .iter()
+            .zip(self.precision.iter())
+            .map(|(m, tau)| sample_normal(&mut self.rng, *m, 1f64 / tau.sqrt()))
+            .collect::<Vec<_>>();
+        (0..samples.len()).fold(0, |best, i| if samples[i] > samples[best] { i } else { best })
+    }
+
+    fn update(&mut self, arm: usize, reward: f64) {
+        let tau_noise = 1f64 / (self.noise_std * self.noise_std);
+        let precision = self.precision[arm] + tau_noise;
+        self.mean[arm] = (self.precision[arm] * self.mean[arm] + tau_noise * reward) / precision;
+        self.precision[arm] = precision;
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Linear models for contextual bandits
+//  - Ridge regression per arm: A_a = λI + Σ x xᵀ, b_a = Σ r x, θ̂_a = A_a⁻¹ b_a
+//  - A_a⁻¹ is kept up to date with the Sherman-Morrison formula
+// └──────────────────────────────────────────────────────────┘
+#[derive(Debug, Clone)]
+pub struct RidgeArms {
+    pub a_inv: Vec<Matrix>,
+    pub b: Vec<Vec<f64>>,
+}
+
+impl RidgeArms {
+    pub fn new(k: usize, d: usize, lambda: f64) -> Self {
+        assert!(lambda > 0f64, "regularization must be positive");
+        Self {
+            a_inv: vec![eye(d) / lambda; k],
+            b: vec![vec![0f64; d]; k],
+        }
+    }
+
+    /// θ̂_a
+    pub fn theta(&self, arm: usize) -> Vec<f64> {
+        &self.a_inv[arm] * &self.b[arm]
+    }
+
+    /// xᵀ A_a⁻¹ x
+    pub fn variance(&self, arm: usize, context: &[f64]) -> f64 {
+        let u = &self.a_inv[arm] * &context.to_vec();
+        dot(&u, context)
+    }
+
+    pub fn update(&mut self, context: &[f64], arm: usize, reward: f64) {
+        // A⁻¹ ← A⁻¹ - (A⁻¹ x)(A⁻¹ x)ᵀ / (1 + xᵀ A⁻¹ x)
+        let u = &self.a_inv[arm] * &context.to_vec();
+        let denom = 1f64 + dot(&u, context);
+        let a_inv = &mut self.a_inv[arm];
+        for i in 0..u.len() {
+            for j in 0..u.len() {
+                a_inv[(i, j)] -= u[i] * u[j] / denom;
+            }
+        }
+
+        for (b, x) in self.b[arm].iter_mut().zip(context.iter()) {
+            *b += reward * x;
+        }
+    }
+}
+
+fn dot(x: &[f64], y: &[f64]) -> f64 {
+    x.iter().zip(y.iter()).map(|(x, y)| x * y).sum()
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  LinUCB (disjoint)
+//  - Selects argmax_a θ̂_aᵀ x + α √(xᵀ A_a⁻¹ x)
+// └──────────────────────────────────────────────────────────┘
+pub struct LinUCB {
+    pub arms: RidgeArms,
+    alpha: f64,
+}
+
+impl LinUCB {
+    /// `k` arms with `d` features and ridge regularization 1
+    pub fn new(k: usize, d: usize, alpha: f64) -> Self {
+        Self {
+            arms: RidgeArms::new(k, d, 1f64),
+            alpha,
+        }
+    }
+
+    pub fn with_lambda(mut self, lambda: f64) -> Self {
+        self.arms = RidgeArms::new(self.arms.b.len(), self.arms.b[0].len(), lambda);
+        self
+    }
+
+    /// Upper confidence bound of every arm for `context`
+    pub fn upper_bounds(&self, context: &[f64]) -> Vec<f64> {
+        (0..self.arms.b.len())
+            .map(|a| dot(&self.arms.theta(a), context) + self.alpha * self.arms.variance(a, context).sqrt())
+            .collect()
+    }
+}
+
+impl ContextualBanditAgent for LinUCB {
+    fn select_arm(&mut self, context: &[f64]) -> usize {
+        let bounds = self.upper_bounds(context);
+        (0..bounds.len()).fold(0, |best, i| if bounds[i] > bounds[best] { i } else { best })
+    }
+
+    fn update(&mut self, context: &[f64], arm: usize, reward: f64) {
+        self.arms.update(context, arm, reward);
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Linear Thompson Sampling
+//  - Samples θ̃_a ~ N(θ̂_a, v² A_a⁻¹) and selects argmax_a θ̃_aᵀ x
+// └──────────────────────────────────────────────────────────┘
+pub struct LinearThompson {
+    pub arms: RidgeArms,
+    v: f64,
+    rng: StdRng,
+}
+
+impl LinearThompson {
+    /// `k` arms with `d` features, ridge regularization 1 and posterior scale `v`
+    pub fn new(k: usize, d: usize, v: f64) -> Self {
+        Self {
+            arms: RidgeArms::new(k, d, 1f64),
+            v,
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    pub fn with_lambda(mut self, lambda: f64) -> Self {
+        self.arms = RidgeArms::new(self.arms.b.len(), self.arms.b[0].len(), lambda);
+        self
+    }
+
+    /// Seed the RNG used for posterior sampling
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    pub fn with_rng(mut self, rng: StdRng) -> Self {
+        self.rng = rng;
+        self
+    }
+}
+
+impl ContextualBanditAgent for LinearThompson {
+    fn select_arm(&mut self, context: &[f64]) -> usize {
+        let samples = (0..self.arms.b.len())
+            .map(|a| {
+                // θ̃ = θ̂ + v L z with L Lᵀ = A⁻¹
+                let l = cholesky(&self.arms.a_inv[a]);
+                let z = (0..context.len())
+                    .map(|_| sample_normal(&mut self.rng, 0f64, 1f64))
+                    .collect::<Vec<_>>();
+                let noise = &l * &z;
+                let theta = self.arms.theta(a);
+                theta
+                    .iter()
+                    .zip(noise.iter())
+                    .zip(context.iter())
+                    .map(|((t, n), x)| (t + self.v * n) * x)
+                    .sum::<f64>()
+            })
+            .collect::<Vec<_>>();
+        (0..samples.len()).fold(0, |best, i| if samples[i] > samples[best] { i } else { best })
+    }
+
+    fn update(&mut self, context: &[f64], arm: usize, reward: f64) {
+        self.arms.update(context, arm, reward);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ridge_arms_inverse_matches_direct_inversion() {
+        let lambda = 0.5;
+        let contexts = [[1.0, 0.5, -1.0], [0.0, 2.0, 1.0], [-1.5, 1.0, 0.5], [0.3, -0.2, 2.0]];
+        let mut arms = RidgeArms::new(2, 3, lambda);
+        let mut a = eye(3) * lambda;
+        for x in contexts.iter() {
+            arms.update(x, 1, 1.0);
+            for i in 0..3 {
+                for j in 0..3 {
+                    a[(i, j)] += x[i] * x[j];
+                }
+            }
+        }
+
+        let a_inv = a.inv();
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((arms.a_inv[1][(i, j)] - a_inv[(i, j)]).abs() < 1e-10);
+                // The other arm is untouched
+                let untouched = if i == j { 1.0 / lambda } else { 0.0 };
+                assert_eq!(arms.a_inv[0][(i, j)], untouched);
+            }
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use peroxide::fuga::*;
+
+pub mod testbed;
+pub mod agent;
+
+/// k-armed bandit: a one-step decision problem with `num_arms` actions
+pub trait Bandit {
+    fn num_arms(&self) -> usize;
+
+    /// Draw a reward for `arm` (non-stationary bandits drift afterwards)
+    fn pull(&mut self, arm: usize) -> f64;
+
+    /// Expected reward of every arm in the current round
+    fn expected_rewards(&self) -> Vec<f64>;
+
+    fn optimal_arm(&self) -> usize {
+        let expected = self.expected_rewards();
+        (0..expected.len())
+            .fold(0, |best, i| if expected[i] > expected[best] { i } else { best })
+    }
+}
+
+/// Agents which play a `Bandit`
+pub trait BanditAgent {
+    fn select_arm(&mut self) -> usize;
+    fn update(&mut self, arm: usize, reward: f64);
+}
+
+/// Per-round record of a bandit run
+///
+/// `regret` is the cumulative pseudo-regret Σ (max_a μ_a - μ_{a_t}).
+#[derive(Debug, Clone, Default)]
+pub struct BanditHistory {
+    pub rewards: Vec<f64>,
+    pub optimal: Vec<bool>,
+    pub regret: Vec<f64>,
+}
+
+/// Play `rounds` rounds of `bandit` with `agent`
+pub fn simulate<B: Bandit, G: BanditAgent>(agent: &mut G, bandit: &mut B, rounds: usize) -> BanditHistory {
+    let mut history = BanditHistory::default();
+    let mut regret = 0f64;
+
+    for _ in 0..rounds {
+        // Expected rewards are taken before the pull, since non-stationary arms drift afterwards
+        let expected = bandit.expected_rewards();
+        let best = expected.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        let arm = agent.select_arm();
+        let reward = bandit.pull(arm);
+        agent.update(arm, reward);
+
+        regret += best - expected[arm];
+        history.rewards.push(reward);
+        history.optimal.push(expected[arm] == best);
+        history.regret.push(regret);
+    }
+
+    history
+}
+
+/// Contextual bandit: every round reveals a feature vector before an arm is pulled
+pub trait ContextualBandit {
+    fn num_arms(&self) -> usize;
+    fn num_features(&self) -> usize;
+
+    /// Start a new round and return its context
+    fn next_context(&mut self) -> Vec<f64>;
+
+    /// Draw a reward for `arm` in the current round
+    fn pull(&mut self, arm: usize) -> f64;
+
+    /// Expected reward of every arm in the current round
+    fn expected_rewards(&self) -> Vec<f64>;
+}
+
+/// Agents which play a `ContextualBandit`
+pub trait ContextualBanditAgent {
+    fn select_arm(&mut self, context: &[f64]) -> usize;
+    fn update(&mut self, context: &[f64], arm: usize, reward: f64);
+}
+
+/// Play `rounds` rounds of a contextual `bandit` with `agent`
+pub fn simulate_contextual<B: ContextualBandit, G: ContextualBanditAgent>(
+    agent: &mut G,
+    bandit: &mut B,
+    rounds: usize,
+) -> BanditHistory {
+    let mut history = BanditHistory::default();
+    let mut regret = 0f64;
+
+    for _ in 0..rounds {
+        let context = bandit.next_context();
+        let expected = bandit.expected_rewards();
+        let best = expected.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        let arm = agent.select_arm(&context);
+        let reward = bandit.pull(arm);
+        agent.update(&context, arm, reward);
+
+        regret += best - expected[arm];
+        history.rewards.push(reward);
+        history.optimal.push(expected[arm] == best);
+        history.regret.push(regret);
+    }
+
+    history
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Samplers on a given RNG (for reproducible runs)
+// └──────────────────────────────────────────────────────────┘
+/// Normal distribution (Box-Muller)
+pub(crate) fn sample_normal<R: Rng + ?Sized>(rng: &mut R, mean: f64, std: f64) -> f64 {
+    let u1: f64 = rng.gen::<f64>().max(f64::MIN_POSITIVE);
+    let u2: f64 = rng.gen();
+    mean + std * (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+/// Gamma distribution with unit scale (Marsaglia & Tsang)
+pub(crate) fn sample_gamma<R: Rng + ?Sized>(rng: &mut R, shape: f64) -> f64 {
+    assert!(shape > 0f64, "shape must be positive");
+    if shape < 1f64 {
+        // Gamma(a) = Gamma(a + 1) U^{1/a}
+        let u: f64 = rng.gen::<f64>().max(f64::MIN_POSITIVE);
+        return sample_gamma(rng, shape + 1f64) * u.powf(1f64 / shape);
+    }
+
+    let d = shape - 1f64 / 3f64;
+    let c = 1f64 / (9f64 * d).sqrt();
+    loop {
+        let x = sample_normal(rng, 0f64, 1f64);
+        let v = (1f64 + c * x).powi(3);
+        if v <= 0f64 {
+            continue;
+        }
+        let u: f64 = rng.gen::<f64>().max(f64::MIN_POSITIVE);
+        if u.ln() < 0.5 * x * x + d - d * v + d * v.ln() {
+            return d * v;
+        }
+    }
+}
+
+/// Beta distribution via two gamma samples
+pub(crate) fn sample_beta<R: Rng + ?Sized>(rng: &mut R, a: f64, b: f64) -> f64 {
+    let x = sample_gamma(rng, a);
+    let y = sample_gamma(rng, b);
+    x / (x + y)
+}
+
+/// Lower triangular L with L Lᵀ = `m` for a symmetric positive definite `m` (Cholesky-Banachiewicz)
+pub(crate) fn cholesky(m: &Matrix) -> Matrix {
+    let n = m.row;
+    let mut l = zeros(n, n);
+    for i in 0..n {
+        for j in 0..=i {
+            let sum = (0..j).map(|k| l[(i, k)] * l[(j, k)]).sum::<f64>();
+            if i == j {
+                // Clamp round-off below zero
+                l[(i, j)] = (m[(i, i)] - sum).max(0f64).sqrt();
+            } else if l[(j, j)] > 0f64 {
+                l[(i, j)] = (m[(i, j)] - sum) / l[(j, j)];
+            }
+        }
+    }
+    l
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cholesky_reconstructs_spd_matrix() {
+        let m = matrix(vec![4.0, 2.0, 0.6, 2.0, 5.0, 1.0, 0.6, 1.0, 3.0], 3, 3, Row);
+        let l = cholesky(&m);
+        let llt = &l * &l.t();
+
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((llt[(i, j)] - m[(i, j)]).abs() < 1e-12);
+                if j > i {
+                    assert_eq!(l[(i, j)], 0.0);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use peroxide::fuga::*;
+
+use crate::bandit::{sample_normal, Bandit, ContextualBandit};
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Gaussian Bandit
+//  - Rewards ~ N(μ_a, σ²)
+//  - Optionally non-stationary: every μ_a takes a N(0, σ_walk²) step after each pull
+// └──────────────────────────────────────────────────────────┘
+#[derive(Debug, Clone)]
+pub struct GaussianBandit {
+    means: Vec<f64>,
+    std: f64,
+    walk_std: f64,
+    rng: StdRng,
+}
+
+impl GaussianBandit {
+    pub fn new(means: Vec<f64>) -> Self {
+        assert!(!means.is_empty(), "bandit needs at least one arm");
+        Self {
+            means,
+            std: 1f64,
+            walk_std: 0f64,
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// The k-armed testbed of Sutton & Barto: μ_a ~ N(0, 1) and unit reward variance
+    pub fn testbed(k: usize, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let means = (0..k).map(|_| sample_normal(&mut rng, 0f64, 1f64)).collect();
+        Self::new(means).with_rng(rng)
+    }
+
+    pub fn with_reward_std(mut self, std: f64) -> Self {
+        self.std = std;
+        self
+    }
+
+    /// Make the arms drift with independent random walks
+    pub fn with_random_walk(mut self, walk_std: f64) -> Self {
+        self.walk_std = walk_std;
+        self
+    }
+
+    /// Seed the RNG used for rewards and drift
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    pub fn with_rng(mut self, rng: StdRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    pub fn get_means(&self) -> &Vec<f64> {
+        &self.means
+    }
+}
+
+impl Bandit for GaussianBandit {
+    fn num_arms(&self) -> usize {
+        self.means.len()
+    }
+
+    fn pull(&mut self, arm: usize) -> f64 {
+        let reward = sample_normal(&mut self.rng, self.means[arm], self.std);
+        if self.walk_std > 0f64 {
+            for mean in self.means.iter_mut() {
+                *mean += sample_normal(&mut self.rng, 0f64, self.walk_std);
+            }
+        }
+        reward
+    }
+
+    fn expected_rewards(&self) -> Vec<f64> {
+        self.means.clone()
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Bernoulli Bandit
+//  - Rewards ~ Bernoulli(p_a)
+//  - Optionally non-stationary: every p_a takes a N(0, σ_walk²) step (clamped to [0, 1]) after each pull
+// └──────────────────────────────────────────────────────────┘
+#[derive(Debug, Clone)]
+pub struct BernoulliBandit {
+    probs: Vec<f64>,
+    walk_std: f64,
+    rng: StdRng,
+}
+
+impl BernoulliBandit {
+    pub fn new(probs: Vec<f64>) -> Self {
+        assert!(!probs.is_empty(), "bandit needs at least one arm");
+        assert!(
+            probs.iter().all(|p| (0f64..=1f64).contains(p)),
+            "probabilities must lie in [0, 1]"
+        );
+        Self {
+            probs,
+            walk_std: 0f64,
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// k arms with p_a ~ U(0, 1)
+    pub fn testbed(k: usize, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let probs = (0..k).map(|_| rng.gen::<f64>()).collect();
+        Self::new(probs).with_rng(rng)
+    }
+
+    /// Make the arms drift with independent random walks
+    pub fn with_random_walk(mut self, walk_std: f64) -> Self {
+        self.walk_std = walk_std;
+        self
+    }
+
+    /// Seed the RNG used for rewards and drift
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    pub fn with_rng(mut self, rng: StdRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    pub fn get_probs(&self) -> &Vec<f64> {
+        &self.probs
+    }
+}
+
+impl Bandit for BernoulliBandit {
+    fn num_arms(&self) -> usize {
+        self.probs.len()
+    }
+
+    fn pull(&mut self, arm: usize) -> f64 {
+        let reward = if self.rng.gen::<f64>() < self.probs[arm] { 1f64 } else { 0f64 };
+        if self.walk_std > 0f64 {
+            for p in self.probs.iter_mut() {
+                *p = (*p + sample_normal(&mut self.rng, 0f64, self.walk_std)).clamp(0f64, 1f64);
+            }
+        }
+        reward
+    }
+
+    fn expected_rewards(&self) -> Vec<f64> {
+        self.probs.clone()
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Linear Contextual Bandit
+//  - Contexts x ~ N(0, I_d), rewards ~ N(θ_aᵀ x, σ²)
+// └──────────────────────────────────────────────────────────┘
+#[derive(Debug, Clone)]
+pub struct LinearBandit {
+    theta: Vec<Vec<f64>>,
+    noise_std: f64,
+    context: Vec<f64>,
+    rng: StdRng,
+}
+
+impl LinearBandit {
+    /// `theta[a]` is the parameter vector of arm `a`
+    pub fn new(theta: Vec<Vec<f64>>) -> Self {
+        assert!(!theta.is_empty(), "bandit needs at least one arm");
+        let d = theta[0].len();
+        assert!(theta.iter().all(|t| t.len() == d), "every arm needs the same number of features");
+        Self {
+            theta,
+            noise_std: 1f64,
+            context: vec![0f64; d],
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// k arms with θ_a ~ N(0, I_d / d), so that θ_aᵀ x has unit variance
+    pub fn random(k: usize, d: usize, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let std = 1f64 / (d as f64).sqrt();
+        let theta = (0..k)
+            .map(|_| (0..d).map(|_| sample_normal(&mut rng, 0f64, std)).collect())
+            .collect();
+        Self::new(theta).with_rng(rng)
+    }
+
+    pub fn with_noise_std(mut self, noise_std: f64) -> Self {
+        self.noise_std = noise_std;
+        self
+    }
+
+    /// Seed the RNG used for contexts and rewards
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    pub fn with_rng(mut self, rng: StdRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    pub fn get_theta(&self) -> &Vec<Vec<f64>> {
+        &self.theta
+    }
+}
+
+impl ContextualBandit for LinearBandit {
+    fn num_arms(&self) -> usize {
+        self.theta.len()
+    }
+
+    fn num_features(&self) -> usize {
+        self.context.len()
+    }
+
+    fn next_context(&mut self) -> Vec<f64> {
+        for x in self.context.iter_mut() {
+            *x = sample_normal(&mut self.rng, 0f64, 1f64);
+        }
+        self.context.clone()
+    }
+
+    fn pull(&mut self, arm: usize) -> f64 {
+        let mean = self.theta[arm].iter().zip(self.context.iter()).map(|(t, x)| t * x).sum::<f64>();
+        sample_normal(&mut self.rng, mean, self.noise_std)
+    }
+
+    fn expected_rewards(&self) -> Vec<f64> {
+        self.theta
+            .iter()
+            .map(|t| t.iter().zip(self.context.iter()).map(|(t, x)| t * x).sum())
+            .collect()
+    }
+}
@@ -0,0 +1,253 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::env::FiniteMdp;
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Dynamic Programming
+//  - Exact planning on finite MDPs with a known transition model
+//  - Ending the episode (next state `None`) contributes zero future value
+// └──────────────────────────────────────────────────────────┘
+#[derive(Debug, Copy, Clone)]
+pub struct DP {
+    pub gamma: f64,
+    tolerance: f64,
+    max_iter: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct DPSolution<S, A> {
+    pub value_function: HashMap<S, f64>,
+    pub policy: HashMap<S, A>,
+    /// Number of sweeps (value iteration) or improvement steps (policy iteration)
+    pub iterations: usize,
+}
+
+impl DP {
+    pub fn new(gamma: f64) -> Self {
+        Self {
+            gamma,
+            tolerance: 1e-8,
+            max_iter: 10_000,
+        }
+    }
+
+    /// Stop sweeping once the largest value change falls below `tolerance`
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// Σ_{s', r} p(s', r | s, a) [r + γ V(s')]
+    pub fn action_value<S: Hash + Eq, A: Clone, E: FiniteMdp<S, A>>(
+        &self,
+        env: &E,
+        value_function: &HashMap<S, f64>,
+        state: &S,
+        action: &A,
+    ) -> f64 {
+        env.transition_distribution(state, &Some(action.clone()))
+            .iter()
+            .map(|(s_next, r, p)| {
+                let v_next = s_next
+                    .as_ref()
+                    .map_or(0f64, |s_next| *value_function.get(s_next).unwrap_or(&0.0));
+                p * (r + self.gamma * v_next)
+            })
+            .sum()
+    }
+
+    /// Action values of every state-action pair under `value_function`
+    pub fn q_values<S: Hash + Eq + Clone, A: Hash + Eq + Clone, E: FiniteMdp<S, A>>(
+        &self,
+        env: &E,
+        value_function: &HashMap<S, f64>,
+    ) -> HashMap<(S, A), f64> {
+        let mut q_table = HashMap::new();
+        for s in env.states() {
+            for a in env.available_actions(&s) {
+                let q = self.action_value(env, value_function, &s, &a);
+                q_table.insert((s.clone(), a), q);
+            }
+        }
+        q_table
+    }
+
+    /// Iterative policy evaluation
+    ///
+    /// `policy` maps a state to `(action, probability)` pairs.
+    pub fn evaluate_policy<S: Hash + Eq + Clone, A: Clone, E: FiniteMdp<S, A>>(
+        &self,
+        env: &E,
+        policy: impl Fn(&S) -> Vec<(A, f64)>,
+    ) -> HashMap<S, f64> {
+        let states = env.states();
+        let mut value_function: HashMap<S, f64> =
+            states.iter().map(|s| (s.clone(), 0f64)).collect();
+
+        for _ in 0..self.max_iter {
+            let mut diff = 0f64;
+            for s in states.iter() {
+                let v = policy(s)
+                    .iter()
+                    .map(|(a, p)| p * self.action_value(env, &value_function, s, a))
+                    .sum::<f64>();
+                let v_old = value_function.insert(s.clone(), v).unwrap();
+                diff = diff.max((v - v_old).abs());
+            }
+            if diff < self.tolerance {
+                break;
+            }
+        }
+
+        value_function
+    }
+
+    /// Greedy policy with respect to `value_function` (ties are broken by action order)
+    pub fn greedy_policy<S: Hash + Eq + Clone, A: Clone, E: FiniteMdp<S, A>>(
+        &self,
+        env: &E,
+        value_function: &HashMap<S, f64>,
+    ) -> HashMap<S, A> {
+        env.states()
+            .into_iter()
+            .filter_map(|s| {
+                self.best_action(env, value_function, &s)
+                    .map(|(a, _)| (s, a))
+            })
+            .collect()
+    }
+
+    pub fn policy_iteration<S: Hash + Eq + Clone, A: Clone + PartialEq, E: FiniteMdp<S, A>>(
+        &self,
+        env: &E,
+    ) -> DPSolution<S, A> {
+        let mut policy: HashMap<S, A> = env
+            .states()
+            .into_iter()
+            .filter_map(|s| env.available_actions(&s).first().cloned().map(|a| (s, a)))
+            .collect();
+
+        let mut iterations = 0;
+        loop {
+            iterations += 1;
+            let value_function = self.evaluate_policy(env, |s| {
+                policy.get(s).map_or(vec![], |a| vec![(a.clone(), 1f64)])
+            });
+
+            // Only switch actions on a strict improvement, to avoid cycling between ties
+            let mut stable = true;
+            for (s, a) in policy.iter_mut() {
+                let q_old = self.action_value(env, &value_function, s, a);
+                if let Some((a_best, q_best)) = self.best_action(env, &value_function, s) {
+                    if q_best > q_old + self.tolerance && a_best != *a {
+                        *a = a_best;
+                        stable = false;
+                    }
+                }
+            }
+
+            if stable || iterations >= self.max_iter {
+                return DPSolution {
+                    value_function,
+                    policy,
+                    iterations,
+                };
+            }
+        }
+    }
+
+    pub fn value_iteration<S: Hash + Eq + Clone, A: Clone, E: FiniteMdp<S, A>>(
+        &self,
+        env: &E,
+    ) -> DPSolution<S, A> {
+        let states = env.states();
+        let mut value_function: HashMap<S, f64> =
+            states.iter().map(|s| (s.clone(), 0f64)).collect();
+
+        let mut iterations = 0;
+        while iterations < self.max_iter {
+            iterations += 1;
+            let mut diff = 0f64;
+            for s in states.iter() {
+                let v = self
+                    .best_action(env, &value_function, s)
+                    .map_or(0f64, |(_, q)| q);
+                let v_old = value_function.insert(s.clone(), v).unwrap();
+                diff = diff.max((v - v_old).abs());
+            }
+            if diff < self.tolerance {
+                break;
+            }
+        }
+
+        let policy = self.greedy_policy(env, &value_function);
+        DPSolution {
+            value_function,
+            policy,
+            iterations,
+        }
+    }
+
+    fn best_action<S: Hash + Eq, A: Clone, E: FiniteMdp<S, A>>(
+        &self,
+        env: &E,
+        value_function: &HashMap<S, f64>,
+        state: &S,
+    ) -> Option<(A, f64)> {
+        let mut best: Option<(A, f64)> = None;
+        for a in env.available_actions(state) {
+            let q = self.action_value(env, value_function, state, &a);
+            if best.as_ref().is_none_or(|(_, q_best)| q > *q_best) {
+                best = Some((a, q));
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::env::tabular::TabularMdp;
+
+    // Two states: from 0, `go` reaches 1 with probability 0.8; in 1, `stay` earns 1 forever
+    // while `go` ends the episode with 5. With γ = 0.9 the optimal values are
+    //   V*(1) = 1 / (1 - γ) = 10
+    //   V*(0) = 0.8 γ V*(1) / (1 - 0.2 γ) = 7.2 / 0.82
+    fn two_state_mdp() -> TabularMdp<usize, &'static str> {
+        TabularMdp::new()
+            .with_transition(0, "stay", Some(0), 0.0, 1.0)
+            .with_transition(0, "go", Some(1), 0.0, 0.8)
+            .with_transition(0, "go", Some(0), 0.0, 0.2)
+            .with_transition(1, "stay", Some(1), 1.0, 1.0)
+            .with_transition(1, "go", None, 5.0, 1.0)
+    }
+
+    #[test]
+    fn value_iteration_matches_closed_form() {
+        let solution = DP::new(0.9).value_iteration(&two_state_mdp());
+
+        assert!((solution.value_function[&1] - 10.0).abs() < 1e-6);
+        assert!((solution.value_function[&0] - 7.2 / 0.82).abs() < 1e-6);
+        assert_eq!(solution.policy[&0], "go");
+        assert_eq!(solution.policy[&1], "stay");
+    }
+
+    #[test]
+    fn policy_iteration_agrees_with_value_iteration() {
+        let env = two_state_mdp();
+        let dp = DP::new(0.9);
+        let vi = dp.value_iteration(&env);
+        let pi = dp.policy_iteration(&env);
+
+        assert_eq!(vi.policy, pi.policy);
+        for s in env.states() {
+            assert!((vi.value_function[&s] - pi.value_function[&s]).abs() < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use crate::env::gridworld::GridWorldAction;
+use crate::env::{Env, ExploringStarts, FiniteMdp};
+use crate::env::episodic::Episodic;
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Cliff Walking (Sutton & Barto, Example 6.6)
+//  - Start at (0, 0), goal at (num_x - 1, 0), the cells between them on the bottom row are the cliff
+//  - -1 per step; stepping into the cliff gives -100 and sends the agent back to the start
+//    without ending the episode
+//  - Entering the goal ends the episode; moves off the grid keep the agent in place
+// └──────────────────────────────────────────────────────────┘
+#[derive(Debug, Clone)]
+pub struct CliffWalking {
+    num_x: usize,
+    num_y: usize,
+}
+
+impl CliffWalking {
+    /// The standard 12 × 4 grid
+    pub fn new() -> Self {
+        Self::with_size(12, 4)
+    }
+
+    pub fn with_size(num_x: usize, num_y: usize) -> Self {
+        assert!(num_x >= 2 && num_y >= 2, "cliff walking needs at least a 2 × 2 grid");
+        Self { num_x, num_y }
+    }
+
+    pub fn get_init_state(&self) -> (usize, usize) {
+        (0, 0)
+    }
+
+    pub fn get_goal_state(&self) -> (usize, usize) {
+        (self.num_x - 1, 0)
+    }
+
+    /// `(num_x, num_y)`
+    pub fn size(&self) -> (usize, usize) {
+        (self.num_x, self.num_y)
+    }
+
+    pub fn is_cliff(&self, state: &(usize, usize)) -> bool {
+        state.1 == 0 && state.0 > 0 && state.0 < self.num_x - 1
+    }
+
+    /// Render the grid (`C` marks the cliff), with `overlay` drawing on top of the other cells
+    pub fn render(&self, overlay: impl Fn(&(usize, usize)) -> Option<char>) -> String {
+        let mut map = String::new();
+        for y in (0..self.num_y).rev() {
+            for x in 0..self.num_x {
+                let cell = (x, y);
+                let c = if self.is_cliff(&cell) {
+                    'C'
+                } else if let Some(c) = overlay(&cell) {
+                    c
+                } else if self.is_goal(&cell) {
+                    'G'
+                } else if cell == self.get_init_state() {
+                    'S'
+                } else {
+                    '.'
+                };
+                map.push(c);
+            }
+            map.push('\n');
+        }
+        map
+    }
+}
+
+impl Default for CliffWalking {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Env<(usize, usize), GridWorldAction> for CliffWalking {
+    fn is_terminal(&self, _state: &(usize, usize)) -> bool {
+        false
+    }
+
+    fn is_goal(&self, state: &(usize, usize)) -> bool {
+        *state == self.get_goal_state()
+    }
+
+    fn transition(
+        &self,
+        state: &(usize, usize),
+        action: &Option<GridWorldAction>,
+    ) -> (Option<(usize, usize)>, f64) {
+        if self.is_goal(state) {
+            return (None, 0.0);
+        }
+
+        let (x, y) = *state;
+        let next_state = match action.as_ref().unwrap() {
+            GridWorldAction::Up => (x, (y + 1).min(self.num_y - 1)),
+            GridWorldAction::Down => (x, y.saturating_sub(1)),
+            GridWorldAction::Left => (x.saturating_sub(1), y),
+            GridWorldAction::Right => ((x + 1).min(self.num_x - 1), y),
+        };
+
+        if self.is_cliff(&next_state) {
+            (Some(self.get_init_state()), -100.0)
+        } else if self.is_goal(&next_state) {
+            (None, -1.0)
+        } else {
+            (Some(next_state), -1.0)
+        }
+    }
+
+    fn available_actions(&self, state: &(usize, usize)) -> Vec<GridWorldAction> {
+        if self.is_cliff(state) {
+            return vec![];
+        }
+        vec![
+            GridWorldAction::Left,
+            GridWorldAction::Right,
+            GridWorldAction::Up,
+            GridWorldAction::Down,
+        ]
+    }
+}
+
+impl FiniteMdp<(usize, usize), GridWorldAction> for CliffWalking {
+    /// Every cell except the cliff
+    fn states(&self) -> Vec<(usize, usize)> {
+        (0..self.num_x)
+            .flat_map(|x| (0..self.num_y).map(move |y| (x, y)))
+            .filter(|cell| !self.is_cliff(cell))
+            .collect()
+    }
+}
+
+impl ExploringStarts<(usize, usize), GridWorldAction> for CliffWalking {}
+
+impl From<CliffWalking> for Episodic<(usize, usize), GridWorldAction, CliffWalking> {
+    fn from(env: CliffWalking) -> Self {
+        let init_state = env.get_init_state();
+        Episodic::new(env, init_state)
+    }
+}
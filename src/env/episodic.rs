@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use peroxide::fuga::*;
+
+use crate::env::{Env, ExploringStarts};
+
+/// Auxiliary diagnostics returned with each step
+pub type Info = HashMap<String, f64>;
+
+/// Result of a single `step`
+///
+/// When `terminated` is true, `observation` is the state in which the episode ended.
+#[derive(Debug, Clone)]
+pub struct Step<S> {
+    pub observation: S,
+    pub reward: f64,
+    pub terminated: bool,
+    pub truncated: bool,
+    pub info: Info,
+}
+
+impl<S> Step<S> {
+    pub fn is_done(&self) -> bool {
+        self.terminated || self.truncated
+    }
+}
+
+/// Stateful episodic interface (`reset` / `step`)
+pub trait EpisodicEnv<S, A> {
+    fn reset(&mut self) -> S;
+    fn step(&mut self, action: &A) -> Step<S>;
+    fn state(&self) -> &S;
+    fn available_actions(&self) -> Vec<A>;
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Episodic Adapter
+// └──────────────────────────────────────────────────────────┘
+/// Wraps any `Env` into an `EpisodicEnv`, sampling transitions from its transition model
+pub struct Episodic<S, A, E: Env<S, A>> {
+    env: E,
+    init_state: S,
+    state: S,
+    t: usize,
+    max_steps: Option<usize>,
+    done: bool,
+    rng: StdRng,
+    _action_type: std::marker::PhantomData<A>,
+}
+
+impl<S: Clone, A: Clone, E: Env<S, A>> Episodic<S, A, E> {
+    pub fn new(env: E, init_state: S) -> Self {
+        Self {
+            env,
+            state: init_state.clone(),
+            init_state,
+            t: 0,
+            max_steps: None,
+            done: false,
+            rng: StdRng::from_entropy(),
+            _action_type: std::marker::PhantomData,
+        }
+    }
+
+    /// Truncate episodes after `max_steps` steps
+    ///
+    /// Returns of a truncated episode are cut short; the `Trainer` therefore does not pass truncated
+    /// episodes to per-episode (Monte Carlo) agents.
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    pub fn get_max_steps(&self) -> Option<usize> {
+        self.max_steps
+    }
+
+    /// Seed the RNG used to sample transitions
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    pub fn env(&self) -> &E {
+        &self.env
+    }
+
+    pub fn get_init_state(&self) -> &S {
+        &self.init_state
+    }
+
+    pub fn get_step_count(&self) -> usize {
+        self.t
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Start a new episode from `state` instead of the initial state
+    pub fn reset_to(&mut self, state: S) -> S {
+        self.state = state;
+        self.t = 0;
+        self.done = false;
+        self.state.clone()
+    }
+
+    /// Start a new episode from a random state-action pair, drawn with the episode RNG
+    ///
+    /// The returned action has to be taken in the first step.
+    pub fn reset_exploring(&mut self) -> (S, A)
+    where
+        E: ExploringStarts<S, A>,
+    {
+        let (s, a) = self.env.exploring_start(&mut self.rng);
+        (self.reset_to(s), a)
+    }
+}
+
+impl<S: Clone, A: Clone, E: Env<S, A>> EpisodicEnv<S, A> for Episodic<S, A, E> {
+    fn reset(&mut self) -> S {
+        self.state = self.init_state.clone();
+        self.t = 0;
+        self.done = false;
+        self.state.clone()
+    }
+
+    fn step(&mut self, action: &A) -> Step<S> {
+        if self.done {
+            panic!("Episode is over! Call reset() first.")
+        }
+
+        let (next_state, reward) =
+            self.env
+                .sample_transition(&self.state, &Some(action.clone()), &mut self.rng);
+        self.t += 1;
+
+        let terminated = next_state.is_none();
+        if let Some(s) = next_state {
+            self.state = s;
+        }
+        let truncated = !terminated && self.max_steps.is_some_and(|m| self.t >= m);
+        self.done = terminated || truncated;
+
+        let mut info = Info::new();
+        info.insert("t".to_string(), self.t as f64);
+
+        Step {
+            observation: self.state.clone(),
+            reward,
+            terminated,
+            truncated,
+            info,
+        }
+    }
+
+    fn state(&self) -> &S {
+        &self.state
+    }
+
+    fn available_actions(&self) -> Vec<A> {
+        self.env.available_actions(&self.state)
+    }
+}
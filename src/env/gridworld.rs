@@ -1,4 +1,6 @@
-use crate::env::Env;
+use crate::env::{Env, ExploringStarts, FiniteMdp, Outcome};
+use crate::env::episodic::Episodic;
+use crate::env::rewards::Rewards;
 
 // ┌──────────────────────────────────────────────────────────┐
 //  Grid World
@@ -10,6 +12,8 @@ pub struct GridWorld {
     init_state: (usize, usize),
     goal_state: (usize, usize),
     terminal_state: Vec<(usize, usize)>,
+    rewards: Rewards<(usize, usize)>,
+    slip: f64,
 }
 
 impl GridWorld {
@@ -26,6 +30,8 @@ impl GridWorld {
             init_state,
             goal_state,
             terminal_state,
+            rewards: Rewards::new(),
+            slip: 0.0,
         }
     }
 
@@ -40,6 +46,26 @@ impl GridWorld {
     pub fn get_terminal_state(&self) -> &Vec<(usize, usize)> {
         &self.terminal_state
     }
+
+    pub fn with_rewards(mut self, rewards: Rewards<(usize, usize)>) -> Self {
+        self.rewards = rewards;
+        self
+    }
+
+    pub fn get_rewards(&self) -> &Rewards<(usize, usize)> {
+        &self.rewards
+    }
+
+    /// Probability that the chosen action is replaced by a uniformly random available action
+    pub fn with_slip(mut self, slip: f64) -> Self {
+        assert!((0.0..=1.0).contains(&slip), "slip probability must be in [0, 1]");
+        self.slip = slip;
+        self
+    }
+
+    pub fn get_slip(&self) -> f64 {
+        self.slip
+    }
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -64,21 +90,46 @@ impl Env<(usize, usize), GridWorldAction> for GridWorld {
         state: &(usize, usize),
         action: &Option<GridWorldAction>,
     ) -> (Option<(usize, usize)>, f64) {
-        if self.is_terminal(state) {
-            (None, -1.0)
-        } else if self.is_goal(state) {
-            (None, 1.0)
+        if self.is_terminal(state) || self.is_goal(state) {
+            self.rewards.exit(self, state)
         } else {
             let action = action.as_ref().unwrap();
-            match action {
-                GridWorldAction::Up => (Some((state.0, state.1 + 1)), 0.0),
-                GridWorldAction::Down => (Some((state.0, state.1 - 1)), 0.0),
-                GridWorldAction::Left => (Some((state.0 - 1, state.1)), 0.0),
-                GridWorldAction::Right => (Some((state.0 + 1, state.1)), 0.0),
-            }
+            let next_state = match action {
+                GridWorldAction::Up => (state.0, state.1 + 1),
+                GridWorldAction::Down => (state.0, state.1 - 1),
+                GridWorldAction::Left => (state.0 - 1, state.1),
+                GridWorldAction::Right => (state.0 + 1, state.1),
+            };
+            self.rewards.enter(self, next_state)
         }
     }
 
+    fn transition_distribution(
+        &self,
+        state: &(usize, usize),
+        action: &Option<GridWorldAction>,
+    ) -> Vec<Outcome<(usize, usize)>> {
+        if self.slip == 0.0 || self.is_terminal(state) || self.is_goal(state) {
+            let (next_state, reward) = self.transition(state, action);
+            return vec![(next_state, reward, 1.0)];
+        }
+
+        let actions = self.available_actions(state);
+        let p_slip = self.slip / actions.len() as f64;
+        actions
+            .iter()
+            .map(|a| {
+                let p = if Some(*a) == *action {
+                    1.0 - self.slip + p_slip
+                } else {
+                    p_slip
+                };
+                let (next_state, reward) = self.transition(state, &Some(*a));
+                (next_state, reward, p)
+            })
+            .collect()
+    }
+
     fn available_actions(&self, state: &(usize, usize)) -> Vec<GridWorldAction> {
         match state {
             (0, 0) => vec![GridWorldAction::Right, GridWorldAction::Up],
@@ -114,3 +165,20 @@ impl Env<(usize, usize), GridWorldAction> for GridWorld {
         }
     }
 }
+
+impl FiniteMdp<(usize, usize), GridWorldAction> for GridWorld {
+    fn states(&self) -> Vec<(usize, usize)> {
+        (0..self.num_x)
+            .flat_map(|x| (0..self.num_y).map(move |y| (x, y)))
+            .collect()
+    }
+}
+
+impl ExploringStarts<(usize, usize), GridWorldAction> for GridWorld {}
+
+impl From<GridWorld> for Episodic<(usize, usize), GridWorldAction, GridWorld> {
+    fn from(env: GridWorld) -> Self {
+        let init_state = env.get_init_state();
+        Episodic::new(env, init_state)
+    }
+}
@@ -1,4 +1,6 @@
-use crate::env::Env;
+use crate::env::{Env, ExploringStarts, FiniteMdp, Outcome};
+use crate::env::episodic::Episodic;
+use crate::env::rewards::Rewards;
 
 // ┌──────────────────────────────────────────────────────────┐
 //  Line World
@@ -9,6 +11,8 @@ pub struct LineWorld {
     init_state: usize,
     goal_state: usize,
     terminal_state: Vec<usize>,
+    rewards: Rewards<usize>,
+    slip: f64,
 }
 
 impl LineWorld {
@@ -23,6 +27,8 @@ impl LineWorld {
             init_state,
             goal_state,
             terminal_state,
+            rewards: Rewards::new(),
+            slip: 0.0,
         }
     }
 
@@ -37,6 +43,26 @@ impl LineWorld {
     pub fn get_terminal_state(&self) -> &Vec<usize> {
         &self.terminal_state
     }
+
+    pub fn with_rewards(mut self, rewards: Rewards<usize>) -> Self {
+        self.rewards = rewards;
+        self
+    }
+
+    pub fn get_rewards(&self) -> &Rewards<usize> {
+        &self.rewards
+    }
+
+    /// Probability that the chosen action is replaced by a uniformly random available action
+    pub fn with_slip(mut self, slip: f64) -> Self {
+        assert!((0.0..=1.0).contains(&slip), "slip probability must be in [0, 1]");
+        self.slip = slip;
+        self
+    }
+
+    pub fn get_slip(&self) -> f64 {
+        self.slip
+    }
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -55,19 +81,44 @@ impl Env<usize, LineWorldAction> for LineWorld {
     }
 
     fn transition(&self, state: &usize, action: &Option<LineWorldAction>) -> (Option<usize>, f64) {
-        if self.is_terminal(state) {
-            (None, -1.0)
-        } else if self.is_goal(state) {
-            (None, 1.0)
+        if self.is_terminal(state) || self.is_goal(state) {
+            self.rewards.exit(self, state)
         } else {
             let action = action.as_ref().unwrap();
-            match action {
-                LineWorldAction::Up => (Some(*state + 1), 0.0),
-                LineWorldAction::Down => (Some(*state - 1), 0.0),
-            }
+            let next_state = match action {
+                LineWorldAction::Up => *state + 1,
+                LineWorldAction::Down => *state - 1,
+            };
+            self.rewards.enter(self, next_state)
         }
     }
 
+    fn transition_distribution(
+        &self,
+        state: &usize,
+        action: &Option<LineWorldAction>,
+    ) -> Vec<Outcome<usize>> {
+        if self.slip == 0.0 || self.is_terminal(state) || self.is_goal(state) {
+            let (next_state, reward) = self.transition(state, action);
+            return vec![(next_state, reward, 1.0)];
+        }
+
+        let actions = self.available_actions(state);
+        let p_slip = self.slip / actions.len() as f64;
+        actions
+            .iter()
+            .map(|a| {
+                let p = if Some(*a) == *action {
+                    1.0 - self.slip + p_slip
+                } else {
+                    p_slip
+                };
+                let (next_state, reward) = self.transition(state, &Some(*a));
+                (next_state, reward, p)
+            })
+            .collect()
+    }
+
     fn available_actions(&self, state: &usize) -> Vec<LineWorldAction> {
         match state {
             0 => vec![LineWorldAction::Up],
@@ -76,3 +127,18 @@ impl Env<usize, LineWorldAction> for LineWorld {
         }
     }
 }
+
+impl FiniteMdp<usize, LineWorldAction> for LineWorld {
+    fn states(&self) -> Vec<usize> {
+        (0..self.num_rows).collect()
+    }
+}
+
+impl ExploringStarts<usize, LineWorldAction> for LineWorld {}
+
+impl From<LineWorld> for Episodic<usize, LineWorldAction, LineWorld> {
+    fn from(env: LineWorld) -> Self {
+        let init_state = env.get_init_state();
+        Episodic::new(env, init_state)
+    }
+}
@@ -0,0 +1,337 @@
+use std::path::Path;
+
+use crate::env::gridworld::GridWorldAction;
+use crate::env::{Env, ExploringStarts, FiniteMdp, Outcome};
+use crate::env::episodic::Episodic;
+use crate::env::rewards::Rewards;
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Maze World
+//  - Grid with walls, loaded from an ASCII map:
+//    `#` wall, `.` empty, `S` start, `G` goal, `X` pit
+//  - The top line of the map is the highest row (`Up` increases y, as in `GridWorld`)
+//  - Every action is available everywhere; moving into a wall or off the map keeps the agent in place
+//  - Rewards follow a `Rewards` specification (by default +1 / -1 when acting upon goals / pits, as in `GridWorld`)
+// └──────────────────────────────────────────────────────────┘
+#[derive(Debug, Clone)]
+pub struct MazeWorld {
+    num_x: usize,
+    num_y: usize,
+    walls: Vec<bool>,
+    init_state: (usize, usize),
+    goals: Vec<(usize, usize)>,
+    pits: Vec<(usize, usize)>,
+    rewards: Rewards<(usize, usize)>,
+    slip: f64,
+}
+
+impl MazeWorld {
+    /// Parse an ASCII map (leading/trailing blank lines and surrounding whitespace are ignored)
+    ///
+    /// Rewards default to `Rewards::new()`; see `with_rewards` and the per-cell builders.
+    pub fn from_ascii(map: &str) -> Result<Self, String> {
+        let lines = map
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+        if lines.is_empty() {
+            return Err("empty map".to_string());
+        }
+
+        let num_x = lines[0].chars().count();
+        let num_y = lines.len();
+        let mut walls = vec![false; num_x * num_y];
+        let mut init_state = None;
+        let mut goals = vec![];
+        let mut pits = vec![];
+
+        for (row, line) in lines.iter().enumerate() {
+            if line.chars().count() != num_x {
+                return Err(format!("line {} has {} cells, expected {}", row + 1, line.chars().count(), num_x));
+            }
+            let y = num_y - 1 - row;
+            for (x, c) in line.chars().enumerate() {
+                match c {
+                    '#' => walls[y * num_x + x] = true,
+                    '.' => (),
+                    'S' => {
+                        if init_state.replace((x, y)).is_some() {
+                            return Err("more than one start cell `S`".to_string());
+                        }
+                    }
+                    'G' => goals.push((x, y)),
+                    'X' => pits.push((x, y)),
+                    _ => return Err(format!("unknown cell `{}` at line {}", c, row + 1)),
+                }
+            }
+        }
+
+        let init_state = init_state.ok_or_else(|| "no start cell `S`".to_string())?;
+        if goals.is_empty() {
+            return Err("no goal cell `G`".to_string());
+        }
+        goals.sort();
+        pits.sort();
+
+        Ok(Self {
+            num_x,
+            num_y,
+            walls,
+            init_state,
+            goals,
+            pits,
+            rewards: Rewards::new(),
+            slip: 0.0,
+        })
+    }
+
+    /// Read an ASCII map from a file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let map = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| format!("cannot read {}: {}", path.as_ref().display(), e))?;
+        Self::from_ascii(&map)
+    }
+
+    /// Replace the reward specification (including rewards set by the builders below)
+    pub fn with_rewards(mut self, rewards: Rewards<(usize, usize)>) -> Self {
+        self.rewards = rewards;
+        self
+    }
+
+    pub fn get_rewards(&self) -> &Rewards<(usize, usize)> {
+        &self.rewards
+    }
+
+    /// Reward of the goal `cell`
+    pub fn with_goal_reward(mut self, cell: (usize, usize), reward: f64) -> Self {
+        assert!(self.goals.contains(&cell), "{:?} is not a goal", cell);
+        self.rewards = self.rewards.with_goal_at(cell, reward);
+        self
+    }
+
+    /// Reward of the pit `cell`
+    pub fn with_pit_reward(mut self, cell: (usize, usize), reward: f64) -> Self {
+        assert!(self.pits.contains(&cell), "{:?} is not a pit", cell);
+        self.rewards = self.rewards.with_pit_at(cell, reward);
+        self
+    }
+
+    /// Reward for moving into `cell`
+    pub fn with_cell_reward(mut self, cell: (usize, usize), reward: f64) -> Self {
+        assert!(!self.is_wall(&cell), "{:?} is a wall", cell);
+        self.rewards = self.rewards.with_cell(cell, reward);
+        self
+    }
+
+    /// Probability that the chosen action is replaced by a uniformly random action
+    pub fn with_slip(mut self, slip: f64) -> Self {
+        assert!((0.0..=1.0).contains(&slip), "slip probability must be in [0, 1]");
+        self.slip = slip;
+        self
+    }
+
+    pub fn get_init_state(&self) -> (usize, usize) {
+        self.init_state
+    }
+
+    pub fn get_goal_states(&self) -> &Vec<(usize, usize)> {
+        &self.goals
+    }
+
+    pub fn get_pits(&self) -> &Vec<(usize, usize)> {
+        &self.pits
+    }
+
+    pub fn get_slip(&self) -> f64 {
+        self.slip
+    }
+
+    /// `(num_x, num_y)`
+    pub fn size(&self) -> (usize, usize) {
+        (self.num_x, self.num_y)
+    }
+
+    /// Whether `cell` is a wall or lies outside the map
+    pub fn is_wall(&self, cell: &(usize, usize)) -> bool {
+        cell.0 >= self.num_x || cell.1 >= self.num_y || self.walls[cell.1 * self.num_x + cell.0]
+    }
+
+    /// Render the map, with `overlay` drawing on top of the non-wall cells
+    pub fn render(&self, overlay: impl Fn(&(usize, usize)) -> Option<char>) -> String {
+        let mut map = String::new();
+        for y in (0..self.num_y).rev() {
+            for x in 0..self.num_x {
+                let cell = (x, y);
+                let c = if self.is_wall(&cell) {
+                    '#'
+                } else if let Some(c) = overlay(&cell) {
+                    c
+                } else if self.goals.contains(&cell) {
+                    'G'
+                } else if self.pits.contains(&cell) {
+                    'X'
+                } else if cell == self.init_state {
+                    'S'
+                } else {
+                    '.'
+                };
+                map.push(c);
+            }
+            map.push('\n');
+        }
+        map
+    }
+
+    /// Cell reached by `action` from `state` (blocked moves stay in place)
+    fn next_cell(&self, state: &(usize, usize), action: &GridWorldAction) -> (usize, usize) {
+        let (x, y) = *state;
+        let next = match action {
+            GridWorldAction::Up => (x, y + 1),
+            GridWorldAction::Down if y > 0 => (x, y - 1),
+            GridWorldAction::Left if x > 0 => (x - 1, y),
+            GridWorldAction::Right => (x + 1, y),
+            _ => return *state,
+        };
+        if self.is_wall(&next) {
+            *state
+        } else {
+            next
+        }
+    }
+}
+
+impl Env<(usize, usize), GridWorldAction> for MazeWorld {
+    fn is_terminal(&self, state: &(usize, usize)) -> bool {
+        self.pits.contains(state)
+    }
+
+    fn is_goal(&self, state: &(usize, usize)) -> bool {
+        self.goals.contains(state)
+    }
+
+    fn transition(
+        &self,
+        state: &(usize, usize),
+        action: &Option<GridWorldAction>,
+    ) -> (Option<(usize, usize)>, f64) {
+        if self.is_terminal(state) || self.is_goal(state) {
+            self.rewards.exit(self, state)
+        } else {
+            let next_state = self.next_cell(state, action.as_ref().unwrap());
+            self.rewards.enter(self, next_state)
+        }
+    }
+
+    fn transition_distribution(
+        &self,
+        state: &(usize, usize),
+        action: &Option<GridWorldAction>,
+    ) -> Vec<Outcome<(usize, usize)>> {
+        if self.slip == 0.0 || self.is_terminal(state) || self.is_goal(state) {
+            let (next_state, reward) = self.transition(state, action);
+            return vec![(next_state, reward, 1.0)];
+        }
+
+        let actions = self.available_actions(state);
+        let p_slip = self.slip / actions.len() as f64;
+        actions
+            .iter()
+            .map(|a| {
+                let p = if Some(*a) == *action {
+                    1.0 - self.slip + p_slip
+                } else {
+                    p_slip
+                };
+                let (next_state, reward) = self.transition(state, &Some(*a));
+                (next_state, reward, p)
+            })
+            .collect()
+    }
+
+    fn available_actions(&self, state: &(usize, usize)) -> Vec<GridWorldAction> {
+        if self.is_wall(state) {
+            return vec![];
+        }
+        vec![
+            GridWorldAction::Left,
+            GridWorldAction::Right,
+            GridWorldAction::Up,
+            GridWorldAction::Down,
+        ]
+    }
+}
+
+impl FiniteMdp<(usize, usize), GridWorldAction> for MazeWorld {
+    fn states(&self) -> Vec<(usize, usize)> {
+        (0..self.num_x)
+            .flat_map(|x| (0..self.num_y).map(move |y| (x, y)))
+            .filter(|cell| !self.is_wall(cell))
+            .collect()
+    }
+}
+
+impl ExploringStarts<(usize, usize), GridWorldAction> for MazeWorld {}
+
+impl From<MazeWorld> for Episodic<(usize, usize), GridWorldAction, MazeWorld> {
+    fn from(env: MazeWorld) -> Self {
+        let init_state = env.get_init_state();
+        Episodic::new(env, init_state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::env::gridworld::GridWorldAction::{Down, Left, Right, Up};
+
+    const MAP: &str = "
+        #G.
+        .#X
+        S..
+    ";
+
+    #[test]
+    fn parses_cells_with_top_line_as_highest_row() {
+        let maze = MazeWorld::from_ascii(MAP).unwrap();
+
+        assert_eq!(maze.size(), (3, 3));
+        assert_eq!(maze.get_init_state(), (0, 0));
+        assert_eq!(maze.get_goal_states(), &vec![(1, 2)]);
+        assert_eq!(maze.get_pits(), &vec![(2, 1)]);
+        assert!(maze.is_wall(&(0, 2)));
+        assert!(maze.is_wall(&(1, 1)));
+        assert!(!maze.is_wall(&(0, 1)));
+        assert!(maze.is_wall(&(3, 0)));
+        assert_eq!(maze.render(|_| None), "#G.\n.#X\nS..\n");
+    }
+
+    #[test]
+    fn rejects_malformed_maps() {
+        let err = |map: &str| MazeWorld::from_ascii(map).unwrap_err();
+
+        assert_eq!(err("  \n "), "empty map");
+        assert_eq!(err("S.G\n.."), "line 2 has 2 cells, expected 3");
+        assert_eq!(err("S.G\n.?."), "unknown cell `?` at line 2");
+        assert_eq!(err("S.G\nS.."), "more than one start cell `S`");
+        assert_eq!(err("..G\n..."), "no start cell `S`");
+        assert_eq!(err("S..\n..X"), "no goal cell `G`");
+    }
+
+    #[test]
+    fn blocked_moves_stay_in_place() {
+        let maze = MazeWorld::from_ascii(MAP).unwrap();
+        let next = |state: (usize, usize), action| maze.transition(&state, &Some(action)).0;
+
+        assert_eq!(next((0, 0), Up), Some((0, 1)));
+        assert_eq!(next((0, 0), Right), Some((1, 0)));
+        // Off the map
+        assert_eq!(next((0, 0), Left), Some((0, 0)));
+        assert_eq!(next((0, 0), Down), Some((0, 0)));
+        assert_eq!(next((2, 2), Up), Some((2, 2)));
+        // Into a wall
+        assert_eq!(next((0, 1), Up), Some((0, 1)));
+        assert_eq!(next((0, 1), Right), Some((0, 1)));
+        assert_eq!(next((1, 0), Up), Some((1, 0)));
+    }
+}
@@ -1,9 +1,154 @@
+use std::{collections::HashMap, hash::Hash};
+use peroxide::fuga::*;
+
 pub mod lineworld;
 pub mod gridworld;
+pub mod mazeworld;
+pub mod cliffwalking;
+pub mod episodic;
+pub mod tabular;
+pub mod rewards;
+
+/// `(next_state, reward, probability)`, where `next_state = None` ends the episode
+pub type Outcome<S> = (Option<S>, f64, f64);
 
 pub trait Env<S, A> {
     fn transition(&self, state: &S, action: &Option<A>) -> (Option<S>, f64);
     fn is_terminal(&self, state: &S) -> bool;
     fn is_goal(&self, state: &S) -> bool;
     fn available_actions(&self, state: &S) -> Vec<A>;
+
+    /// Distribution over `(next_state, reward, probability)` outcomes
+    ///
+    /// Deterministic environments do not need to override this: the default
+    /// wraps `transition` as a single outcome with probability one.
+    fn transition_distribution(&self, state: &S, action: &Option<A>) -> Vec<Outcome<S>> {
+        let (next_state, reward) = self.transition(state, action);
+        vec![(next_state, reward, 1.0)]
+    }
+
+    /// Sample `(next_state, reward)` from `transition_distribution` with the given RNG
+    fn sample_transition<R: Rng + ?Sized>(
+        &self,
+        state: &S,
+        action: &Option<A>,
+        rng: &mut R,
+    ) -> (Option<S>, f64)
+    where
+        Self: Sized,
+    {
+        let outcomes = self.transition_distribution(state, action);
+        assert!(!outcomes.is_empty(), "No outcome for this state-action pair!");
+        let n = outcomes.len();
+        let u: f64 = rng.gen();
+
+        let mut acc = 0f64;
+        let mut index = n - 1;
+        for (i, (_, _, p)) in outcomes.iter().enumerate() {
+            acc += p;
+            if u < acc {
+                index = i;
+                break;
+            }
+        }
+
+        let (next_state, reward, _) = outcomes.into_iter().nth(index).unwrap();
+        (next_state, reward)
+    }
+}
+
+/// Environments with a finite, enumerable state space
+///
+/// Together with `Env::transition_distribution` this exposes the full model of the MDP.
+pub trait FiniteMdp<S, A>: Env<S, A> {
+    fn states(&self) -> Vec<S>;
+
+    fn num_states(&self) -> usize {
+        self.states().len()
+    }
+
+    /// Action set of every state
+    fn state_actions(&self) -> Vec<(S, Vec<A>)> {
+        self.states()
+            .into_iter()
+            .map(|s| {
+                let actions = self.available_actions(&s);
+                (s, actions)
+            })
+            .collect()
+    }
+
+    /// Dense index of every state, in the order of `states`
+    fn state_index(&self) -> HashMap<S, usize>
+    where
+        S: Hash + Eq,
+    {
+        self.states().into_iter().enumerate().map(|(i, s)| (s, i)).collect()
+    }
+
+    /// P(s' | s, a), where `next_state = None` means the episode ends
+    fn transition_probability(&self, state: &S, action: &A, next_state: &Option<S>) -> f64
+    where
+        S: PartialEq,
+        A: Clone,
+    {
+        self.transition_distribution(state, &Some(action.clone()))
+            .iter()
+            .filter(|(s, _, _)| s == next_state)
+            .map(|(_, _, p)| p)
+            .sum()
+    }
+
+    /// E[r | s, a]
+    fn expected_reward(&self, state: &S, action: &A) -> f64
+    where
+        A: Clone,
+    {
+        self.transition_distribution(state, &Some(action.clone()))
+            .iter()
+            .map(|(_, r, p)| p * r)
+            .sum()
+    }
+
+    /// `(state, action, outcomes)` for every state-action pair
+    ///
+    /// Outcomes are `(next_state, reward, probability)` as in `Env::transition_distribution`.
+    fn transition_table(&self) -> Vec<(S, A, Vec<Outcome<S>>)>
+    where
+        S: Clone,
+        A: Clone,
+    {
+        let mut table = vec![];
+        for (s, actions) in self.state_actions() {
+            for a in actions {
+                let outcomes = self.transition_distribution(&s, &Some(a.clone()));
+                table.push((s.clone(), a, outcomes));
+            }
+        }
+        table
+    }
+}
+
+/// Environments which can start an episode from an arbitrary state-action pair
+pub trait ExploringStarts<S, A>: FiniteMdp<S, A> {
+    /// States an episode may start from (all states which are neither terminal nor goal)
+    fn start_states(&self) -> Vec<S> {
+        self.states()
+            .into_iter()
+            .filter(|s| !self.is_terminal(s) && !self.is_goal(s) && !self.available_actions(s).is_empty())
+            .collect()
+    }
+
+    /// Draw a start state uniformly from `start_states` and a first action uniformly from its actions
+    fn exploring_start<R: Rng + ?Sized>(&self, rng: &mut R) -> (S, A) {
+        let states = self.start_states();
+        let n = states.len();
+        assert!(n > 0, "No state to start from!");
+        let s = states.into_iter().nth(rng.gen_range(0..n)).unwrap();
+
+        let actions = self.available_actions(&s);
+        let m = actions.len();
+        let a = actions.into_iter().nth(rng.gen_range(0..m)).unwrap();
+        (s, a)
+    }
 }
@@ -0,0 +1,125 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::env::Env;
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Reward Specification
+//  - `step` for every move, plus the reward of the entered cell
+//  - `goal` / `pit` (per-cell overrides possible) for reaching a goal or terminal state
+//  - By default goals and pits pay out on the action taken in them (the episode ends then);
+//    with `with_reward_on_entry` they pay out on arrival and the episode ends immediately
+// └──────────────────────────────────────────────────────────┘
+#[derive(Debug, Clone)]
+pub struct Rewards<S> {
+    step: f64,
+    goal: f64,
+    pit: f64,
+    goal_at: HashMap<S, f64>,
+    pit_at: HashMap<S, f64>,
+    cells: HashMap<S, f64>,
+    on_entry: bool,
+}
+
+impl<S: Hash + Eq + Clone> Rewards<S> {
+    /// 0 per step, +1 for goals and -1 for pits, paid on exit
+    pub fn new() -> Self {
+        Self {
+            step: 0.0,
+            goal: 1.0,
+            pit: -1.0,
+            goal_at: HashMap::new(),
+            pit_at: HashMap::new(),
+            cells: HashMap::new(),
+            on_entry: false,
+        }
+    }
+
+    /// Reward of every move (e.g. -1 for shortest paths)
+    pub fn with_step(mut self, step: f64) -> Self {
+        self.step = step;
+        self
+    }
+
+    pub fn with_goal(mut self, goal: f64) -> Self {
+        self.goal = goal;
+        self
+    }
+
+    pub fn with_pit(mut self, pit: f64) -> Self {
+        self.pit = pit;
+        self
+    }
+
+    /// Reward of the goal `cell`, overriding `goal`
+    pub fn with_goal_at(mut self, cell: S, reward: f64) -> Self {
+        self.goal_at.insert(cell, reward);
+        self
+    }
+
+    /// Reward of the pit `cell`, overriding `pit`
+    pub fn with_pit_at(mut self, cell: S, reward: f64) -> Self {
+        self.pit_at.insert(cell, reward);
+        self
+    }
+
+    /// Extra reward for every move into `cell` (including blocked moves which stay in it)
+    pub fn with_cell(mut self, cell: S, reward: f64) -> Self {
+        self.cells.insert(cell, reward);
+        self
+    }
+
+    /// Pay goal and pit rewards on arrival and end the episode there
+    pub fn with_reward_on_entry(mut self) -> Self {
+        self.on_entry = true;
+        self
+    }
+
+    pub fn get_step(&self) -> f64 {
+        self.step
+    }
+
+    pub fn goal_reward(&self, cell: &S) -> f64 {
+        *self.goal_at.get(cell).unwrap_or(&self.goal)
+    }
+
+    pub fn pit_reward(&self, cell: &S) -> f64 {
+        *self.pit_at.get(cell).unwrap_or(&self.pit)
+    }
+
+    pub fn cell_reward(&self, cell: &S) -> f64 {
+        *self.cells.get(cell).unwrap_or(&0.0)
+    }
+
+    pub fn is_on_entry(&self) -> bool {
+        self.on_entry
+    }
+
+    /// Outcome of acting in the goal or terminal `state` of `env`
+    pub fn exit<A, E: Env<S, A>>(&self, env: &E, state: &S) -> (Option<S>, f64) {
+        if self.on_entry {
+            (None, 0.0)
+        } else if env.is_terminal(state) {
+            (None, self.pit_reward(state))
+        } else {
+            (None, self.goal_reward(state))
+        }
+    }
+
+    /// Outcome of moving from a regular state of `env` into `next`
+    pub fn enter<A, E: Env<S, A>>(&self, env: &E, next: S) -> (Option<S>, f64) {
+        let reward = self.step + self.cell_reward(&next);
+        if self.on_entry && env.is_terminal(&next) {
+            (None, reward + self.pit_reward(&next))
+        } else if self.on_entry && env.is_goal(&next) {
+            (None, reward + self.goal_reward(&next))
+        } else {
+            (Some(next), reward)
+        }
+    }
+}
+
+impl<S: Hash + Eq + Clone> Default for Rewards<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
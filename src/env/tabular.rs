@@ -0,0 +1,152 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::env::{Env, FiniteMdp, Outcome};
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Tabular MDP
+//  - Finite MDP given by explicit transition tables
+// └──────────────────────────────────────────────────────────┘
+#[derive(Debug, Clone)]
+pub struct TabularMdp<S, A> {
+    states: Vec<S>,
+    actions: HashMap<S, Vec<A>>,
+    transitions: HashMap<(S, A), Vec<Outcome<S>>>,
+    terminal_state: Vec<S>,
+    goal_state: Vec<S>,
+}
+
+impl<S: Hash + Eq + Clone, A: Hash + Eq + Clone> TabularMdp<S, A> {
+    pub fn new() -> Self {
+        Self {
+            states: vec![],
+            actions: HashMap::new(),
+            transitions: HashMap::new(),
+            terminal_state: vec![],
+            goal_state: vec![],
+        }
+    }
+
+    /// Copy the full model of any finite MDP into tables
+    pub fn from_mdp<E: FiniteMdp<S, A>>(env: &E) -> Self {
+        let mut mdp = Self::new();
+        for s in env.states() {
+            mdp.add_state(&s);
+            if env.is_terminal(&s) {
+                mdp.terminal_state.push(s.clone());
+            }
+            if env.is_goal(&s) {
+                mdp.goal_state.push(s.clone());
+            }
+        }
+        for (s, a, outcomes) in env.transition_table() {
+            for (s_next, r, p) in outcomes {
+                mdp.add_transition(s.clone(), a.clone(), s_next, r, p);
+            }
+        }
+        mdp
+    }
+
+    /// Add the outcome `(next_state, reward)` with probability `p` to `(state, action)`
+    ///
+    /// `next_state = None` ends the episode.
+    pub fn add_transition(&mut self, state: S, action: A, next_state: Option<S>, reward: f64, p: f64) {
+        self.add_state(&state);
+        if let Some(s) = next_state.as_ref() {
+            self.add_state(s);
+        }
+
+        let actions = self.actions.get_mut(&state).unwrap();
+        if !actions.contains(&action) {
+            actions.push(action.clone());
+        }
+        self.transitions
+            .entry((state, action))
+            .or_default()
+            .push((next_state, reward, p));
+    }
+
+    pub fn with_transition(mut self, state: S, action: A, next_state: Option<S>, reward: f64, p: f64) -> Self {
+        self.add_transition(state, action, next_state, reward, p);
+        self
+    }
+
+    pub fn with_terminal_state(mut self, terminal_state: Vec<S>) -> Self {
+        self.terminal_state = terminal_state;
+        self
+    }
+
+    pub fn with_goal_state(mut self, goal_state: Vec<S>) -> Self {
+        self.goal_state = goal_state;
+        self
+    }
+
+    pub fn get_terminal_state(&self) -> &Vec<S> {
+        &self.terminal_state
+    }
+
+    pub fn get_goal_state(&self) -> &Vec<S> {
+        &self.goal_state
+    }
+
+    /// Whether the outcome probabilities of every state-action pair sum to one (within `tol`)
+    pub fn is_normalized(&self, tol: f64) -> bool {
+        self.transitions.values().all(|outcomes| {
+            let total = outcomes.iter().map(|(_, _, p)| p).sum::<f64>();
+            (total - 1f64).abs() <= tol
+        })
+    }
+
+    fn add_state(&mut self, state: &S) {
+        if !self.actions.contains_key(state) {
+            self.states.push(state.clone());
+            self.actions.insert(state.clone(), vec![]);
+        }
+    }
+}
+
+impl<S: Hash + Eq + Clone, A: Hash + Eq + Clone> Default for TabularMdp<S, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Hash + Eq + Clone, A: Hash + Eq + Clone> Env<S, A> for TabularMdp<S, A> {
+    /// Most likely outcome of `(state, action)`; use `sample_transition` to draw from the table
+    fn transition(&self, state: &S, action: &Option<A>) -> (Option<S>, f64) {
+        let outcomes = self.transition_distribution(state, action);
+        let (s_next, r, _) = outcomes
+            .into_iter()
+            .reduce(|best, o| if o.2 > best.2 { o } else { best })
+            .unwrap();
+        (s_next, r)
+    }
+
+    fn is_terminal(&self, state: &S) -> bool {
+        self.terminal_state.contains(state)
+    }
+
+    fn is_goal(&self, state: &S) -> bool {
+        self.goal_state.contains(state)
+    }
+
+    fn available_actions(&self, state: &S) -> Vec<A> {
+        self.actions.get(state).cloned().unwrap_or_default()
+    }
+
+    /// Outcomes listed for `(state, action)`
+    ///
+    /// A pair without listed outcomes (or no action at all) ends the episode with zero reward.
+    fn transition_distribution(&self, state: &S, action: &Option<A>) -> Vec<Outcome<S>> {
+        action
+            .as_ref()
+            .and_then(|a| self.transitions.get(&(state.clone(), a.clone())))
+            .cloned()
+            .unwrap_or_else(|| vec![(None, 0.0, 1.0)])
+    }
+}
+
+impl<S: Hash + Eq + Clone, A: Hash + Eq + Clone> FiniteMdp<S, A> for TabularMdp<S, A> {
+    fn states(&self) -> Vec<S> {
+        self.states.clone()
+    }
+}
@@ -1,4 +1,7 @@
 pub mod policy;
 pub mod agent;
 pub mod env;
+pub mod trainer;
+pub mod dp;
+pub mod bandit;
 pub mod prelude;
@@ -1,7 +1,87 @@
+use std::{collections::HashMap, hash::Hash};
 use peroxide::fuga::*;
 
 pub trait Policy<A> {
     fn select_action(&mut self, action_rewards: &[(A, f64)]) -> Option<A>;
+
+    /// Probability of selecting each candidate, in the order of `action_rewards`
+    ///
+    /// Candidates with equal values share the probability of a tie.
+    /// Required so that expected and off-policy updates use the policy's actual distribution.
+    fn action_probabilities(&self, action_rewards: &[(A, f64)]) -> Vec<f64>;
+
+    /// Probability of selecting `action` among `action_rewards` (zero if it is not a candidate)
+    fn action_probability(&self, action_rewards: &[(A, f64)], action: &A) -> f64
+    where
+        A: PartialEq,
+    {
+        self.action_probabilities(action_rewards)
+            .into_iter()
+            .zip(action_rewards.iter())
+            .filter(|(_, (a, _))| a == action)
+            .map(|(p, _)| p)
+            .sum()
+    }
+
+    /// Entropy (in nats) of the action distribution over `action_rewards`
+    fn entropy(&self, action_rewards: &[(A, f64)]) -> f64 {
+        self.action_probabilities(action_rewards)
+            .into_iter()
+            .filter(|p| *p > 0f64)
+            .map(|p| -p * p.ln())
+            .sum()
+    }
+}
+
+/// Action selection in a given state
+///
+/// Stateless policies ignore the state; policies with per-state statistics (e.g. `UCBPolicy`) override
+/// these methods. Agents select through this trait, so that such statistics are kept per state.
+pub trait StatePolicy<S, A>: Policy<A> {
+    /// Select an action in `state`
+    ///
+    /// Selecting does not count as taking the action: a hand-written loop must call
+    /// `observe_action_at` for every action it executes (the `Trainer` does this),
+    /// otherwise a counting policy such as `UCBPolicy` treats every action as untried.
+    fn select_action_at(&mut self, _state: &S, action_rewards: &[(A, f64)]) -> Option<A> {
+        self.select_action(action_rewards)
+    }
+
+    /// Probability of selecting each candidate in `state`, in the order of `action_rewards`
+    fn action_probabilities_at(&self, _state: &S, action_rewards: &[(A, f64)]) -> Vec<f64> {
+        self.action_probabilities(action_rewards)
+    }
+
+    /// Probability of selecting `action` among `action_rewards` in `state` (zero if it is not a candidate)
+    fn action_probability_at(&self, state: &S, action_rewards: &[(A, f64)], action: &A) -> f64
+    where
+        A: PartialEq,
+    {
+        self.action_probabilities_at(state, action_rewards)
+            .into_iter()
+            .zip(action_rewards.iter())
+            .filter(|(_, (a, _))| a == action)
+            .map(|(p, _)| p)
+            .sum()
+    }
+
+    /// Record that `action` was actually taken in `state`
+    ///
+    /// Selections are not always executed (e.g. the bootstrap action of a truncated episode),
+    /// so policies which count visits (e.g. `UCBPolicy`) update their statistics here.
+    fn observe_action_at(&mut self, _state: &S, _action: &A) {}
+}
+
+/// Policies with a tunable amount of exploration
+pub trait Explore {
+    /// Advance the exploration schedule by one step (e.g. decay epsilon)
+    fn decay(&mut self);
+    /// Switch to greedy action selection
+    fn eval(&mut self);
+    /// Switch back to exploratory action selection
+    fn train(&mut self);
+    /// Whether the policy is in evaluation (greedy) mode
+    fn is_eval(&self) -> bool;
 }
 
 // ┌──────────────────────────────────────────────────────────┐
@@ -11,6 +91,7 @@ pub struct EGreedyPolicy<A> {
     epsilon: f64,
     decay: f64,
     random: bool,
+    rng: StdRng,
     _action_type: std::marker::PhantomData<A>,
 }
 
@@ -20,10 +101,22 @@ impl<A: Clone> EGreedyPolicy<A> {
             epsilon,
             decay,
             random: true,
+            rng: StdRng::from_entropy(),
             _action_type: std::marker::PhantomData,
         }
     }
 
+    /// Seed the RNG used for exploration and tie-breaking
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    pub fn with_rng(mut self, rng: StdRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
     pub fn decay_epsilon(&mut self) {
         self.epsilon *= self.decay;
     }
@@ -31,6 +124,28 @@ impl<A: Clone> EGreedyPolicy<A> {
     pub fn eval(&mut self) {
         self.random = false;
     }
+
+    pub fn train(&mut self) {
+        self.random = true;
+    }
+}
+
+impl<A: Clone> Explore for EGreedyPolicy<A> {
+    fn decay(&mut self) {
+        self.decay_epsilon();
+    }
+
+    fn eval(&mut self) {
+        self.random = false;
+    }
+
+    fn train(&mut self) {
+        self.random = true;
+    }
+
+    fn is_eval(&self) -> bool {
+        !self.random
+    }
 }
 
 impl<A: Clone> Policy<A> for EGreedyPolicy<A> {
@@ -41,12 +156,10 @@ impl<A: Clone> Policy<A> for EGreedyPolicy<A> {
 
         let epsilon = self.epsilon;
 
-        let u = Uniform(0f64, 1f64);
-        let sample = u.sample(1)[0];
+        let sample: f64 = self.rng.gen();
 
         if sample < epsilon && self.random {
-            let mut rng = thread_rng();
-            Some(action_rewards.choose(&mut rng).unwrap().0.clone())
+            Some(action_rewards.choose(&mut self.rng).unwrap().0.clone())
         } else {
             let mut max_reward = action_rewards[0].1;
             let mut max_actions = vec![];
@@ -60,16 +173,39 @@ impl<A: Clone> Policy<A> for EGreedyPolicy<A> {
                 }
             }
 
-            let mut rng = thread_rng();
-            Some(max_actions.choose(&mut rng).unwrap().clone())
+            Some(max_actions.choose(&mut self.rng).unwrap().clone())
         }
     }
+
+    fn action_probabilities(&self, action_rewards: &[(A, f64)]) -> Vec<f64> {
+        if action_rewards.is_empty() {
+            return vec![];
+        }
+
+        let epsilon = if self.random { self.epsilon } else { 0f64 };
+        let n = action_rewards.len() as f64;
+
+        // Ties share the greedy probability mass equally
+        let max_reward = action_rewards.iter().map(|(_, r)| *r).fold(f64::NEG_INFINITY, f64::max);
+        let num_max = action_rewards.iter().filter(|(_, r)| *r == max_reward).count() as f64;
+
+        action_rewards
+            .iter()
+            .map(|(_, r)| {
+                let greedy = if *r == max_reward { (1f64 - epsilon) / num_max } else { 0f64 };
+                epsilon / n + greedy
+            })
+            .collect()
+    }
 }
 
+impl<S, A: Clone> StatePolicy<S, A> for EGreedyPolicy<A> {}
+
 pub struct EGreedyPolicyMin<A> {
     epsilon: f64,
     decay: f64,
     random: bool,
+    rng: StdRng,
     _action_type: std::marker::PhantomData<A>,
 }
 
@@ -79,10 +215,22 @@ impl<A: Clone> EGreedyPolicyMin<A> {
             epsilon,
             decay,
             random: true,
+            rng: StdRng::from_entropy(),
             _action_type: std::marker::PhantomData,
         }
     }
 
+    /// Seed the RNG used for exploration and tie-breaking
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    pub fn with_rng(mut self, rng: StdRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
     pub fn decay_epsilon(&mut self) {
         self.epsilon *= self.decay;
     }
@@ -90,6 +238,28 @@ impl<A: Clone> EGreedyPolicyMin<A> {
     pub fn eval(&mut self) {
         self.random = false;
     }
+
+    pub fn train(&mut self) {
+        self.random = true;
+    }
+}
+
+impl<A: Clone> Explore for EGreedyPolicyMin<A> {
+    fn decay(&mut self) {
+        self.decay_epsilon();
+    }
+
+    fn eval(&mut self) {
+        self.random = false;
+    }
+
+    fn train(&mut self) {
+        self.random = true;
+    }
+
+    fn is_eval(&self) -> bool {
+        !self.random
+    }
 }
 
 impl<A: Clone> Policy<A> for EGreedyPolicyMin<A> {
@@ -100,12 +270,10 @@ impl<A: Clone> Policy<A> for EGreedyPolicyMin<A> {
 
         let epsilon = self.epsilon;
 
-        let u = Uniform(0f64, 1f64);
-        let sample = u.sample(1)[0];
+        let sample: f64 = self.rng.gen();
 
         if sample < epsilon && self.random {
-            let mut rng = thread_rng();
-            Some(action_rewards.choose(&mut rng).unwrap().0.clone())
+            Some(action_rewards.choose(&mut self.rng).unwrap().0.clone())
         } else {
             let mut min_reward = action_rewards[0].1;
             let mut min_actions = vec![];
@@ -119,9 +287,362 @@ impl<A: Clone> Policy<A> for EGreedyPolicyMin<A> {
                 }
             }
 
-            let mut rng = thread_rng();
-            Some(min_actions.choose(&mut rng).unwrap().clone())
+            Some(min_actions.choose(&mut self.rng).unwrap().clone())
+        }
+    }
+
+    fn action_probabilities(&self, action_rewards: &[(A, f64)]) -> Vec<f64> {
+        if action_rewards.is_empty() {
+            return vec![];
+        }
+
+        let epsilon = if self.random { self.epsilon } else { 0f64 };
+        let n = action_rewards.len() as f64;
+
+        // Ties share the greedy probability mass equally
+        let min_reward = action_rewards.iter().map(|(_, r)| *r).fold(f64::INFINITY, f64::min);
+        let num_min = action_rewards.iter().filter(|(_, r)| *r == min_reward).count() as f64;
+
+        action_rewards
+            .iter()
+            .map(|(_, r)| {
+                let greedy = if *r == min_reward { (1f64 - epsilon) / num_min } else { 0f64 };
+                epsilon / n + greedy
+            })
+            .collect()
+    }
+}
+
+impl<S, A: Clone> StatePolicy<S, A> for EGreedyPolicyMin<A> {}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Softmax (Boltzmann) Policy
+//  - π(a) ∝ exp(Q(a) / τ), computed with log-sum-exp
+//  - Greedy in evaluation mode (ties are broken uniformly)
+// └──────────────────────────────────────────────────────────┘
+pub struct SoftmaxPolicy<A> {
+    temperature: f64,
+    decay: f64,
+    min_temperature: f64,
+    random: bool,
+    rng: StdRng,
+    _action_type: std::marker::PhantomData<A>,
+}
+
+impl<A: Clone> SoftmaxPolicy<A> {
+    pub fn new(temperature: f64, decay: f64) -> Self {
+        assert!(temperature > 0f64, "temperature must be positive");
+        Self {
+            temperature,
+            decay,
+            min_temperature: 1e-3,
+            random: true,
+            rng: StdRng::from_entropy(),
+            _action_type: std::marker::PhantomData,
+        }
+    }
+
+    /// Lower bound of the temperature schedule
+    pub fn with_min_temperature(mut self, min_temperature: f64) -> Self {
+        assert!(min_temperature > 0f64, "minimum temperature must be positive");
+        self.min_temperature = min_temperature;
+        self
+    }
+
+    /// Seed the RNG used for sampling
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    pub fn with_rng(mut self, rng: StdRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    pub fn get_temperature(&self) -> f64 {
+        self.temperature
+    }
+
+    pub fn decay_temperature(&mut self) {
+        self.temperature = (self.temperature * self.decay).max(self.min_temperature);
+    }
+
+    pub fn eval(&mut self) {
+        self.random = false;
+    }
+
+    pub fn train(&mut self) {
+        self.random = true;
+    }
+}
+
+impl<A: Clone> Explore for SoftmaxPolicy<A> {
+    fn decay(&mut self) {
+        self.decay_temperature();
+    }
+
+    fn eval(&mut self) {
+        self.random = false;
+    }
+
+    fn train(&mut self) {
+        self.random = true;
+    }
+
+    fn is_eval(&self) -> bool {
+        !self.random
+    }
+}
+
+impl<A: Clone> Policy<A> for SoftmaxPolicy<A> {
+    fn select_action(&mut self, action_rewards: &[(A, f64)]) -> Option<A> {
+        if action_rewards.is_empty() {
+            return None;
+        }
+
+        let probs = self.action_probabilities(action_rewards);
+        let sample: f64 = self.rng.gen();
+
+        let mut cumulative = 0f64;
+        for ((a, _), p) in action_rewards.iter().zip(probs.iter()) {
+            cumulative += p;
+            if sample < cumulative {
+                return Some(a.clone());
+            }
+        }
+
+        // Rounding: fall back to the last action with non-zero probability
+        action_rewards
+            .iter()
+            .zip(probs.iter())
+            .rev()
+            .find(|(_, p)| **p > 0f64)
+            .map(|((a, _), _)| a.clone())
+    }
+
+    fn action_probabilities(&self, action_rewards: &[(A, f64)]) -> Vec<f64> {
+        if action_rewards.is_empty() {
+            return vec![];
+        }
+
+        let max_reward = action_rewards.iter().map(|(_, r)| *r).fold(f64::NEG_INFINITY, f64::max);
+
+        if !self.random {
+            let num_max = action_rewards.iter().filter(|(_, r)| *r == max_reward).count() as f64;
+            return action_rewards
+                .iter()
+                .map(|(_, r)| if *r == max_reward { 1f64 / num_max } else { 0f64 })
+                .collect();
+        }
+
+        // exp(x - max) never overflows and the greedy term is exactly one
+        let weights = action_rewards
+            .iter()
+            .map(|(_, r)| ((r - max_reward) / self.temperature).exp())
+            .collect::<Vec<_>>();
+        let total = weights.iter().sum::<f64>();
+        weights.into_iter().map(|w| w / total).collect()
+    }
+}
+
+impl<S, A: Clone> StatePolicy<S, A> for SoftmaxPolicy<A> {}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Upper Confidence Bound (UCB1) Policy
+//  - Selects argmax_a Q(s, a) + c √(ln N(s) / N(s, a)), untried actions first
+//  - Counts are kept per state `S` and updated by `observe_action_at`, i.e. only for actions taken
+//    (debug builds panic when many actions are selected without any being observed)
+//  - The stateless methods use `S::default()`, the single state `()` of a multi-armed bandit
+// └──────────────────────────────────────────────────────────┘
+pub struct UCBPolicy<A, S = ()> {
+    c: f64,
+    decay: f64,
+    random: bool,
+    state_count: HashMap<S, usize>,
+    action_count: HashMap<(S, A), usize>,
+    selections: usize,
+    rng: StdRng,
+}
+
+/// Exploratory selections allowed before a `UCBPolicy` expects an observed action
+const UNOBSERVED_SELECTIONS: usize = 1000;
+
+impl<A: Clone + Hash + Eq, S: Clone + Hash + Eq> UCBPolicy<A, S> {
+    pub fn new(c: f64, decay: f64) -> Self {
+        Self {
+            c,
+            decay,
+            random: true,
+            state_count: HashMap::new(),
+            action_count: HashMap::new(),
+            selections: 0,
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// Seed the RNG used for tie-breaking
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    pub fn with_rng(mut self, rng: StdRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    pub fn get_c(&self) -> f64 {
+        self.c
+    }
+
+    pub fn decay_c(&mut self) {
+        self.c *= self.decay;
+    }
+
+    pub fn eval(&mut self) {
+        self.random = false;
+    }
+
+    pub fn train(&mut self) {
+        self.random = true;
+    }
+
+    /// N(s)
+    pub fn state_count(&self, state: &S) -> usize {
+        *self.state_count.get(state).unwrap_or(&0)
+    }
+
+    /// N(s, a)
+    pub fn action_count(&self, state: &S, action: &A) -> usize {
+        *self.action_count.get(&(state.clone(), action.clone())).unwrap_or(&0)
+    }
+
+    pub fn reset_counts(&mut self) {
+        self.state_count.clear();
+        self.action_count.clear();
+        self.selections = 0;
+    }
+
+    /// Candidates to choose uniformly from: untried actions, or those maximizing the upper bound
+    ///
+    /// In evaluation mode the bonus is dropped and the greedy actions are returned.
+    fn best_candidates(&self, state: &S, action_rewards: &[(A, f64)]) -> Vec<usize> {
+        let scores = if self.random {
+            let untried = (0..action_rewards.len())
+                .filter(|i| self.action_count(state, &action_rewards[*i].0) == 0)
+                .collect::<Vec<_>>();
+            if !untried.is_empty() {
+                return untried;
+            }
+
+            let ln_n = (self.state_count(state).max(1) as f64).ln();
+            action_rewards
+                .iter()
+                .map(|(a, r)| {
+                    let n = self.action_count(state, a) as f64;
+                    r + self.c * (ln_n / n).sqrt()
+                })
+                .collect::<Vec<_>>()
+        } else {
+            action_rewards.iter().map(|(_, r)| *r).collect::<Vec<_>>()
+        };
+
+        let max_score = scores.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        (0..scores.len()).filter(|i| scores[*i] == max_score).collect()
+    }
+}
+
+impl<A: Clone + Hash + Eq, S: Clone + Hash + Eq> Explore for UCBPolicy<A, S> {
+    fn decay(&mut self) {
+        self.decay_c();
+    }
+
+    fn eval(&mut self) {
+        self.random = false;
+    }
+
+    fn train(&mut self) {
+        self.random = true;
+    }
+
+    fn is_eval(&self) -> bool {
+        !self.random
+    }
+}
+
+impl<A: Clone + Hash + Eq, S: Clone + Hash + Eq + Default> Policy<A> for UCBPolicy<A, S> {
+    fn select_action(&mut self, action_rewards: &[(A, f64)]) -> Option<A> {
+        self.select_action_at(&S::default(), action_rewards)
+    }
+
+    fn action_probabilities(&self, action_rewards: &[(A, f64)]) -> Vec<f64> {
+        self.action_probabilities_at(&S::default(), action_rewards)
+    }
+}
+
+impl<A: Clone + Hash + Eq, S: Clone + Hash + Eq + Default> StatePolicy<S, A> for UCBPolicy<A, S> {
+    fn select_action_at(&mut self, state: &S, action_rewards: &[(A, f64)]) -> Option<A> {
+        if action_rewards.is_empty() {
+            return None;
+        }
+
+        if self.random {
+            self.selections += 1;
+            debug_assert!(
+                self.selections < UNOBSERVED_SELECTIONS || !self.state_count.is_empty(),
+                "UCBPolicy selects actions which are never observed: call `observe_action_at` for each action taken"
+            );
         }
+
+        let i = *self.best_candidates(state, action_rewards).choose(&mut self.rng).unwrap();
+        Some(action_rewards[i].0.clone())
+    }
+
+    fn action_probabilities_at(&self, state: &S, action_rewards: &[(A, f64)]) -> Vec<f64> {
+        if action_rewards.is_empty() {
+            return vec![];
+        }
+
+        let best = self.best_candidates(state, action_rewards);
+        let p = 1f64 / best.len() as f64;
+        (0..action_rewards.len())
+            .map(|i| if best.contains(&i) { p } else { 0f64 })
+            .collect()
+    }
+
+    fn observe_action_at(&mut self, state: &S, action: &A) {
+        *self.state_count.entry(state.clone()).or_insert(0) += 1;
+        *self.action_count.entry((state.clone(), action.clone())).or_insert(0) += 1;
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ucb_counts_per_state() {
+        let mut policy = UCBPolicy::<usize, (usize, usize)>::new(1.0, 1.0).with_seed(0);
+        policy.observe_action_at(&(0, 1), &0);
+        policy.observe_action_at(&(0, 1), &1);
+        policy.observe_action_at(&(1, 0), &1);
+
+        assert_eq!(policy.state_count(&(0, 1)), 2);
+        assert_eq!(policy.state_count(&(1, 0)), 1);
+        assert_eq!(policy.action_count(&(1, 0), &0), 0);
+        assert_eq!(policy.action_count(&(1, 0), &1), 1);
+        // Only action 0 is untried in (1, 0)
+        assert_eq!(policy.select_action_at(&(1, 0), &[(0, 0.0), (1, 10.0)]), Some(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "never observed")]
+    #[cfg(debug_assertions)]
+    fn ucb_rejects_unobserved_selections() {
+        let mut policy = UCBPolicy::<usize>::new(1.0, 1.0).with_seed(0);
+        for _ in 0..UNOBSERVED_SELECTIONS {
+            policy.select_action(&[(0, 0.0), (1, 0.0)]);
+        }
+    }
+}
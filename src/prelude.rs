@@ -1,3 +1,9 @@
-pub use crate::env::Env;
-pub use crate::agent::{Agent, QEveryVisitMC, VEveryVisitMC, QTD0, QTD0Min};
-pub use crate::policy::{Policy, EGreedyPolicy, EGreedyPolicyMin};
+pub use crate::env::{Env, ExploringStarts, FiniteMdp};
+pub use crate::env::episodic::{EpisodicEnv, Episodic, Step};
+pub use crate::env::tabular::TabularMdp;
+pub use crate::agent::{Agent, QEveryVisitMC, VEveryVisitMC, QFirstVisitMC, VFirstVisitMC, MCES, OffPolicyMCPrediction, OffPolicyMCControl, Sampling, QTD0, QTD0Min, SARSA, ExpectedSARSA, DoubleQTD0, DoubleQTD0Min, VNStepTD, NStepSARSA, NStepQ, TDLambda, SARSALambda, WatkinsQLambda, Trace, StepSize, ValueTable, QTable, DynaQ, PrioritizedSweeping};
+pub use crate::policy::{Policy, StatePolicy, Explore, EGreedyPolicy, EGreedyPolicyMin, SoftmaxPolicy, UCBPolicy};
+pub use crate::dp::{DP, DPSolution};
+pub use crate::trainer::{Trainer, Trainable, Transition, Learning, Callback, Flow, History};
+pub use crate::bandit::{Bandit, BanditAgent, BanditHistory, ContextualBandit, ContextualBanditAgent};
+pub use crate::bandit::agent::{ActionValueBandit, GradientBandit, BernoulliThompson, GaussianThompson, LinUCB, LinearThompson};
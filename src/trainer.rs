@@ -0,0 +1,541 @@
+use std::io::{self, Stdout, Write};
+
+use crate::agent::Agent;
+use crate::env::episodic::{Episodic, EpisodicEnv};
+use crate::env::{Env, ExploringStarts};
+use crate::policy::{Explore, StatePolicy};
+
+/// A single transition recorded while running an episode
+///
+/// * `actions` are the actions available in `state`
+/// * `next_state` is `None` when the episode terminated
+/// * `next_action` is the action already selected in `next_state` (on-policy agents bootstrap from it)
+/// * `next_actions` are the actions available in `next_state`
+#[derive(Debug, Clone)]
+pub struct Transition<S, A> {
+    pub state: S,
+    pub action: A,
+    pub actions: Vec<A>,
+    pub reward: f64,
+    pub next_state: Option<S>,
+    pub next_action: Option<A>,
+    pub next_actions: Vec<A>,
+    pub truncated: bool,
+}
+
+/// When an agent learns from experience
+///
+/// `PerEpisode` agents are only updated with episodes which terminated: a truncated episode is skipped.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Learning {
+    PerStep,
+    PerEpisode,
+}
+
+/// Agents which can be driven by a `Trainer`
+pub trait Trainable<S, A, P: StatePolicy<S, A>, E: Env<S, A>>: Agent<S, A, P, E> {
+    fn learning(&self) -> Learning;
+
+    /// Build the `Information` for `update` from the transitions of the current episode
+    ///
+    /// Per-step agents receive the episode so far and learn from its last transition.
+    /// The policy is passed for agents whose targets depend on action probabilities.
+    fn information(&self, episode: &[Transition<S, A>], policy: &P) -> Self::Information;
+
+    /// Called before the first step of every episode
+    fn begin_episode(&mut self) {}
+}
+
+/// Whether training should go on after a callback
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Flow {
+    Continue,
+    Stop,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct EpisodeSummary {
+    pub episode: usize,
+    pub steps: usize,
+    pub total_return: f64,
+    pub truncated: bool,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct EvalSummary {
+    pub episode: usize,
+    pub mean_return: f64,
+    pub mean_steps: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    pub returns: Vec<f64>,
+    pub lengths: Vec<usize>,
+    pub evals: Vec<EvalSummary>,
+}
+
+/// Hooks invoked by the `Trainer`
+///
+/// `G` is the agent type and `P` the policy type.
+pub trait Callback<S, A, G, P> {
+    fn on_episode_start(&mut self, _episode: usize, _agent: &mut G, _policy: &mut P) {}
+
+    fn on_step(&mut self, _episode: usize, _transition: &Transition<S, A>) {}
+
+    fn on_episode_end(
+        &mut self,
+        _summary: &EpisodeSummary,
+        _agent: &mut G,
+        _policy: &mut P,
+    ) -> Flow {
+        Flow::Continue
+    }
+
+    fn on_eval(&mut self, _summary: &EvalSummary) -> Flow {
+        Flow::Continue
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Trainer
+// └──────────────────────────────────────────────────────────┘
+pub struct Trainer<S, A, G, P> {
+    episodes: usize,
+    eval_every: Option<usize>,
+    eval_episodes: usize,
+    eval_max_steps: usize,
+    callbacks: Vec<Box<dyn Callback<S, A, G, P>>>,
+}
+
+impl<S: Clone, A: Clone, G, P: StatePolicy<S, A> + Explore> Trainer<S, A, G, P> {
+    pub fn new(episodes: usize) -> Self {
+        Self {
+            episodes,
+            eval_every: None,
+            eval_episodes: 0,
+            eval_max_steps: 1000,
+            callbacks: vec![],
+        }
+    }
+
+    /// Run `episodes` greedy evaluation episodes every `every` training episodes
+    pub fn with_eval(mut self, every: usize, episodes: usize) -> Self {
+        assert!(every > 0, "evaluation interval must be positive");
+        self.eval_every = Some(every);
+        self.eval_episodes = episodes;
+        self
+    }
+
+    /// Cut every evaluation episode after `max_steps` steps (default: 1000)
+    ///
+    /// A greedy policy may cycle forever when the environment does not truncate episodes itself.
+    pub fn with_eval_max_steps(mut self, max_steps: usize) -> Self {
+        assert!(max_steps > 0, "evaluation episodes need at least one step");
+        self.eval_max_steps = max_steps;
+        self
+    }
+
+    pub fn with_callback<C: Callback<S, A, G, P> + 'static>(mut self, callback: C) -> Self {
+        self.callbacks.push(Box::new(callback));
+        self
+    }
+
+    pub fn train<E: Env<S, A>>(
+        &mut self,
+        agent: &mut G,
+        policy: &mut P,
+        env: &mut Episodic<S, A, E>,
+    ) -> History
+    where
+        G: Trainable<S, A, P, E>,
+    {
+        self.run(agent, policy, env, |env| (env.reset(), None))
+    }
+
+    /// Train with exploring starts
+    ///
+    /// Every episode starts from a state-action pair drawn uniformly by `Episodic::reset_exploring`;
+    /// the agent selects its actions from the second step on.
+    /// The environment must truncate episodes (`Episodic::with_max_steps`): exploring-starts agents
+    /// such as `MCES` act greedily and may loop forever in an environment with cycles.
+    pub fn train_exploring_starts<E: ExploringStarts<S, A>>(
+        &mut self,
+        agent: &mut G,
+        policy: &mut P,
+        env: &mut Episodic<S, A, E>,
+    ) -> History
+    where
+        G: Trainable<S, A, P, E>,
+    {
+        assert!(
+            env.get_max_steps().is_some(),
+            "exploring starts need `Episodic::with_max_steps`: a greedy policy may never terminate"
+        );
+        self.run(agent, policy, env, |env| {
+            let (s, a) = env.reset_exploring();
+            (s, Some(a))
+        })
+    }
+
+    /// Training loop; `start` resets the environment and may force the first action
+    fn run<E: Env<S, A>>(
+        &mut self,
+        agent: &mut G,
+        policy: &mut P,
+        env: &mut Episodic<S, A, E>,
+        mut start: impl FnMut(&mut Episodic<S, A, E>) -> (S, Option<A>),
+    ) -> History
+    where
+        G: Trainable<S, A, P, E>,
+    {
+        let mut history = History::default();
+
+        for episode in 0..self.episodes {
+            for callback in self.callbacks.iter_mut() {
+                callback.on_episode_start(episode, agent, policy);
+            }
+            agent.begin_episode();
+
+            let mut transitions: Vec<Transition<S, A>> = vec![];
+            let mut total_return = 0f64;
+            let mut truncated = false;
+
+            let (mut state, first_action) = start(env);
+            let mut action = match first_action {
+                Some(a) => Some(a),
+                None => agent.select_action(&state, policy, env.env()),
+            };
+
+            while let Some(a) = action {
+                let actions = env.available_actions();
+                policy.observe_action_at(&state, &a);
+                let step = env.step(&a);
+                total_return += step.reward;
+                truncated = step.truncated;
+
+                let (next_state, next_action, next_actions) = if step.terminated {
+                    (None, None, vec![])
+                } else {
+                    let next_action = agent.select_action(&step.observation, policy, env.env());
+                    (Some(step.observation.clone()), next_action, env.available_actions())
+                };
+
+                transitions.push(Transition {
+                    state,
+                    action: a,
+                    actions,
+                    reward: step.reward,
+                    next_state: next_state.clone(),
+                    next_action: next_action.clone(),
+                    next_actions,
+                    truncated: step.truncated,
+                });
+
+                for callback in self.callbacks.iter_mut() {
+                    callback.on_step(episode, transitions.last().unwrap());
+                }
+
+                if agent.learning() == Learning::PerStep {
+                    let info = agent.information(&transitions, policy);
+                    agent.update(&info);
+                }
+
+                match next_state {
+                    Some(s) if !step.is_done() => {
+                        state = s;
+                        action = next_action;
+                    }
+                    _ => break,
+                }
+            }
+
+            // Monte Carlo returns of a truncated episode miss their tail, so it is not learned from
+            if agent.learning() == Learning::PerEpisode && !transitions.is_empty() && !truncated {
+                let info = agent.information(&transitions, policy);
+                agent.update(&info);
+            }
+
+            let summary = EpisodeSummary {
+                episode,
+                steps: transitions.len(),
+                total_return,
+                truncated,
+            };
+            history.returns.push(total_return);
+            history.lengths.push(transitions.len());
+
+            let mut flow = Flow::Continue;
+            for callback in self.callbacks.iter_mut() {
+                if callback.on_episode_end(&summary, agent, policy) == Flow::Stop {
+                    flow = Flow::Stop;
+                }
+            }
+
+            if let Some(every) = self.eval_every {
+                if (episode + 1) % every == 0 {
+                    let mut eval = self.evaluate(agent, policy, env, self.eval_episodes);
+                    eval.episode = episode;
+                    history.evals.push(eval);
+                    for callback in self.callbacks.iter_mut() {
+                        if callback.on_eval(&eval) == Flow::Stop {
+                            flow = Flow::Stop;
+                        }
+                    }
+                }
+            }
+
+            if flow == Flow::Stop {
+                break;
+            }
+        }
+
+        history
+    }
+
+    /// Run greedy episodes without learning
+    ///
+    /// The policy is switched to evaluation mode and restored to its previous mode afterwards.
+    /// Each episode is cut after the evaluation step limit (see `with_eval_max_steps`).
+    pub fn evaluate<E: Env<S, A>>(
+        &self,
+        agent: &G,
+        policy: &mut P,
+        env: &mut Episodic<S, A, E>,
+        episodes: usize,
+    ) -> EvalSummary
+    where
+        G: Agent<S, A, P, E>,
+    {
+        let was_eval = policy.is_eval();
+        policy.eval();
+
+        let mut total_return = 0f64;
+        let mut total_steps = 0usize;
+        for _ in 0..episodes {
+            let mut state = env.reset();
+            let mut steps = 0usize;
+            while let Some(a) = agent.select_action(&state, policy, env.env()) {
+                let step = env.step(&a);
+                total_return += step.reward;
+                steps += 1;
+                if step.is_done() || steps >= self.eval_max_steps {
+                    break;
+                }
+                state = step.observation;
+            }
+            total_steps += steps;
+        }
+
+        if !was_eval {
+            policy.train();
+        }
+
+        let n = episodes.max(1) as f64;
+        EvalSummary {
+            episode: 0,
+            mean_return: total_return / n,
+            mean_steps: total_steps as f64 / n,
+        }
+    }
+}
+
+// ┌──────────────────────────────────────────────────────────┐
+//  Callbacks
+// └──────────────────────────────────────────────────────────┘
+/// Decay the exploration of the policy at the end of every episode
+pub struct ExplorationDecay;
+
+impl<S, A, G, P: Explore> Callback<S, A, G, P> for ExplorationDecay {
+    fn on_episode_end(&mut self, _summary: &EpisodeSummary, _agent: &mut G, policy: &mut P) -> Flow {
+        policy.decay();
+        Flow::Continue
+    }
+}
+
+/// Stop training when the evaluation return stops improving
+pub struct EarlyStopping {
+    patience: usize,
+    min_delta: f64,
+    target: Option<f64>,
+    best: f64,
+    wait: usize,
+}
+
+impl EarlyStopping {
+    pub fn new(patience: usize) -> Self {
+        Self {
+            patience,
+            min_delta: 0.0,
+            target: None,
+            best: f64::NEG_INFINITY,
+            wait: 0,
+        }
+    }
+
+    /// Minimum improvement of the mean evaluation return to reset the patience
+    pub fn with_min_delta(mut self, min_delta: f64) -> Self {
+        self.min_delta = min_delta;
+        self
+    }
+
+    /// Stop as soon as the mean evaluation return reaches `target`
+    pub fn with_target(mut self, target: f64) -> Self {
+        self.target = Some(target);
+        self
+    }
+}
+
+impl<S, A, G, P> Callback<S, A, G, P> for EarlyStopping {
+    fn on_eval(&mut self, summary: &EvalSummary) -> Flow {
+        if self.target.is_some_and(|t| summary.mean_return >= t) {
+            return Flow::Stop;
+        }
+
+        if summary.mean_return > self.best + self.min_delta {
+            self.best = summary.mean_return;
+            self.wait = 0;
+            Flow::Continue
+        } else {
+            self.wait += 1;
+            if self.wait >= self.patience {
+                Flow::Stop
+            } else {
+                Flow::Continue
+            }
+        }
+    }
+}
+
+/// Write a one-line summary every `every` episodes (and after every evaluation) to stdout or `with_writer`
+pub struct PrintProgress<W: Write = Stdout> {
+    every: usize,
+    writer: W,
+}
+
+impl PrintProgress {
+    pub fn new(every: usize) -> Self {
+        assert!(every > 0, "progress interval must be positive");
+        Self {
+            every,
+            writer: io::stdout(),
+        }
+    }
+}
+
+impl<W: Write> PrintProgress<W> {
+    /// Write the progress to `writer` instead of stdout
+    pub fn with_writer<V: Write>(self, writer: V) -> PrintProgress<V> {
+        PrintProgress {
+            every: self.every,
+            writer,
+        }
+    }
+
+    pub fn into_writer(self) -> W {
+        self.writer
+    }
+}
+
+impl<S, A, G, P, W: Write> Callback<S, A, G, P> for PrintProgress<W> {
+    fn on_episode_end(&mut self, summary: &EpisodeSummary, _agent: &mut G, _policy: &mut P) -> Flow {
+        if (summary.episode + 1) % self.every == 0 {
+            writeln!(
+                self.writer,
+                "episode: {}, steps: {}, return: {:.4}",
+                summary.episode + 1,
+                summary.steps,
+                summary.total_return
+            )
+            .expect("failed to write progress");
+        }
+        Flow::Continue
+    }
+
+    fn on_eval(&mut self, summary: &EvalSummary) -> Flow {
+        writeln!(
+            self.writer,
+            "eval @ {}: mean steps: {:.2}, mean return: {:.4}",
+            summary.episode + 1,
+            summary.mean_steps,
+            summary.mean_return
+        )
+        .expect("failed to write progress");
+        Flow::Continue
+    }
+}
+
+/// Wrap a closure as an `on_episode_start` hook
+pub struct OnEpisodeStart<F>(pub F);
+
+impl<S, A, G, P, F: FnMut(usize, &mut G, &mut P)> Callback<S, A, G, P> for OnEpisodeStart<F> {
+    fn on_episode_start(&mut self, episode: usize, agent: &mut G, policy: &mut P) {
+        (self.0)(episode, agent, policy)
+    }
+}
+
+/// Wrap a closure as an `on_episode_end` hook
+pub struct OnEpisodeEnd<F>(pub F);
+
+impl<S, A, G, P, F: FnMut(&EpisodeSummary, &mut G, &mut P) -> Flow> Callback<S, A, G, P>
+    for OnEpisodeEnd<F>
+{
+    fn on_episode_end(&mut self, summary: &EpisodeSummary, agent: &mut G, policy: &mut P) -> Flow {
+        (self.0)(summary, agent, policy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::{DoubleQTD0, QTD0};
+    use crate::env::cliffwalking::CliffWalking;
+    use crate::env::gridworld::{GridWorld, GridWorldAction};
+    use crate::policy::EGreedyPolicy;
+
+    type S = (usize, usize);
+    type A = GridWorldAction;
+    type P = EGreedyPolicy<A>;
+    type G = DoubleQTD0<S, A, P, GridWorld>;
+
+    fn seeded_run(seed: u64) -> History {
+        let grid = GridWorld::new(4, 4, (0, 0), (3, 3), vec![(1, 1), (2, 2)]);
+        let mut env = Episodic::from(grid).with_max_steps(100).with_seed(seed);
+        let mut policy = P::new(0.2, 0.99).with_seed(seed);
+        let mut agent = G::new(0.9, 0.5, 0.0).with_seed(seed);
+
+        Trainer::<S, A, G, P>::new(50)
+            .with_eval(10, 2)
+            .train(&mut agent, &mut policy, &mut env)
+    }
+
+    #[test]
+    fn seeded_runs_are_reproducible() {
+        let a = seeded_run(42);
+        let b = seeded_run(42);
+
+        assert_eq!(a.returns, b.returns);
+        assert_eq!(a.lengths, b.lengths);
+        assert_eq!(a.evals.len(), b.evals.len());
+        for (x, y) in a.evals.iter().zip(b.evals.iter()) {
+            assert_eq!(x.mean_return, y.mean_return);
+            assert_eq!(x.mean_steps, y.mean_steps);
+        }
+    }
+
+    #[test]
+    fn evaluation_stops_without_env_truncation() {
+        type G = QTD0<S, A, P, CliffWalking>;
+        let mut env = Episodic::from(CliffWalking::new()).with_seed(0);
+        let mut policy = P::new(0.1, 1.0).with_seed(0);
+        let mut agent = G::new(1.0, 0.5, 0.0).with_seed(0);
+
+        let history = Trainer::<S, A, G, P>::new(5)
+            .with_eval(1, 1)
+            .with_eval_max_steps(50)
+            .train(&mut agent, &mut policy, &mut env);
+
+        assert_eq!(history.evals.len(), 5);
+        assert!(history.evals.iter().all(|e| e.mean_steps <= 50.0));
+    }
+}